@@ -0,0 +1,275 @@
+use chrono::{Duration, NaiveTime};
+use futures::channel::mpsc::UnboundedSender;
+use gpui::WindowAppearance;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+    time::{Duration as StdDuration, SystemTime},
+};
+use tracing::{info, warn};
+
+use crate::{
+    scheduler::{FadeStyle, ScheduleDays, ScheduleEntry, ScheduleZone},
+    theme::{InterpolatableTheme, ThemeLabel},
+};
+
+// --- APPEARANCE SIGNAL ---
+//
+// Desktop-integration scripts tell us whether the desktop is light or dark by
+// writing "light" or "dark" to a watched file, or to a local socket, e.g.
+//
+//     echo dark > ~/.cache/zed-theme-fader/appearance
+//     echo dark | nc -U /tmp/zed-theme-fader.sock
+//
+// Profiles configured with a light/dark theme pair then fade to the matching
+// theme. Nothing here depends on a particular desktop service.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Appearance {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl Appearance {
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "light" => Some(Self::Light),
+            "dark" => Some(Self::Dark),
+            _ => None,
+        }
+    }
+}
+
+impl From<WindowAppearance> for Appearance {
+    fn from(appearance: WindowAppearance) -> Self {
+        match appearance {
+            WindowAppearance::Light | WindowAppearance::VibrantLight => Self::Light,
+            WindowAppearance::Dark | WindowAppearance::VibrantDark => Self::Dark,
+        }
+    }
+}
+
+impl fmt::Display for Appearance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Light => f.write_str("light"),
+            Self::Dark => f.write_str("dark"),
+        }
+    }
+}
+
+/// Where appearance changes are read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AppearanceSource {
+    /// A file holding "light" or "dark", re-read whenever it is modified.
+    File(PathBuf),
+    /// A Unix socket; each connection sends "light" or "dark" (the last line wins).
+    Socket(PathBuf),
+}
+
+impl fmt::Display for AppearanceSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "file {:?}", path),
+            Self::Socket(path) => write!(f, "socket {:?}", path),
+        }
+    }
+}
+
+/// The themes a profile fades between as the appearance changes.
+#[derive(Clone)]
+pub struct AppearanceConfig {
+    pub light: InterpolatableTheme,
+    pub dark: InterpolatableTheme,
+    pub light_label: ThemeLabel,
+    pub dark_label: ThemeLabel,
+    pub fade_duration: Duration,
+    pub style: FadeStyle,
+}
+
+impl AppearanceConfig {
+    pub fn theme(&self, appearance: Appearance) -> &InterpolatableTheme {
+        match appearance {
+            Appearance::Light => &self.light,
+            Appearance::Dark => &self.dark,
+        }
+    }
+
+    pub fn label(&self, appearance: Appearance) -> &ThemeLabel {
+        match appearance {
+            Appearance::Light => &self.light_label,
+            Appearance::Dark => &self.dark_label,
+        }
+    }
+
+    pub fn theme_name(&self, appearance: Appearance) -> &str {
+        &self.label(appearance).name
+    }
+
+    /// A schedule that shows the theme for `appearance` all day, so switching
+    /// to it fades from whatever is on screen like any other schedule change.
+    pub fn schedule(&self, appearance: Appearance) -> Arc<Vec<ScheduleEntry>> {
+        Arc::new(vec![ScheduleEntry {
+            time: NaiveTime::MIN.into(),
+            zone: ScheduleZone::SystemLocal,
+            theme: self.theme(appearance).clone(),
+            label: self.label(appearance).clone(),
+            fade_duration: Duration::zero(),
+            style: self.style,
+            days: ScheduleDays::every_day(),
+        }])
+    }
+}
+
+/// How often a watched file's modification time is checked, a socket for new
+/// connections, and either watcher for whether anyone still listens.
+const POLL_INTERVAL: StdDuration = StdDuration::from_millis(500);
+/// How long a socket client gets to send its appearance before it is dropped.
+#[cfg(unix)]
+const SOCKET_READ_TIMEOUT: StdDuration = StdDuration::from_secs(1);
+
+/// The appearance currently written to `path`, if it holds one.
+pub fn read_appearance_file(path: &Path) -> Option<Appearance> {
+    Appearance::parse(&std::fs::read_to_string(path).ok()?)
+}
+
+/// Passes a watcher's appearances on to `sender`, dropping repeats.
+struct Reporter {
+    last: Appearance,
+    sender: UnboundedSender<Appearance>,
+}
+
+impl Reporter {
+    /// Sends `appearance` if it differs from the last one; false once nobody listens.
+    fn report(&mut self, appearance: Appearance) -> bool {
+        if appearance == self.last {
+            return self.is_listening();
+        }
+        info!("Appearance changed to {}.", appearance);
+        self.last = appearance;
+        self.sender.unbounded_send(appearance).is_ok()
+    }
+
+    fn is_listening(&self) -> bool {
+        !self.sender.is_closed()
+    }
+}
+
+/// Watches `source` on a background thread, sending the appearance it reports
+/// to `sender` whenever it differs from the last one (starting from `initial`).
+/// The thread stops within `POLL_INTERVAL` of the receiver being dropped.
+/// Fails only if a socket can't be set up.
+pub fn watch_appearance(
+    source: AppearanceSource,
+    initial: Appearance,
+    sender: UnboundedSender<Appearance>,
+) -> std::io::Result<()> {
+    let reporter = Reporter {
+        last: initial,
+        sender,
+    };
+    match source {
+        AppearanceSource::File(path) => {
+            watch_file(path, reporter);
+            Ok(())
+        }
+        #[cfg(unix)]
+        AppearanceSource::Socket(path) => watch_socket(path, reporter),
+        #[cfg(not(unix))]
+        AppearanceSource::Socket(_) => Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "appearance sockets are only supported on Unix",
+        )),
+    }
+}
+
+fn watch_file(path: PathBuf, mut reporter: Reporter) {
+    thread::spawn(move || {
+        let mut last_modified: Option<SystemTime> = None;
+        while reporter.is_listening() {
+            let modified = std::fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            if modified.is_some() && modified != last_modified {
+                last_modified = modified;
+                match read_appearance_file(&path) {
+                    Some(appearance) => {
+                        if !reporter.report(appearance) {
+                            break;
+                        }
+                    }
+                    None => warn!(
+                        "Appearance file {:?} doesn't hold \"light\" or \"dark\".",
+                        path
+                    ),
+                }
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+        info!("Stopped watching appearance file {:?}.", path);
+    });
+}
+
+#[cfg(unix)]
+fn watch_socket(path: PathBuf, mut reporter: Reporter) -> std::io::Result<()> {
+    use std::io::{BufRead, BufReader};
+    use std::os::unix::{fs::FileTypeExt, net::UnixListener};
+
+    // A socket left behind by an earlier run would make bind fail. Anything
+    // else at the path is the user's, and is never removed.
+    match std::fs::symlink_metadata(&path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(&path)?,
+        Ok(_) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("{:?} exists and is not a socket", path),
+            ));
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    let listener = UnixListener::bind(&path)?;
+    // Accepting without blocking lets the thread notice, between connections,
+    // that nobody listens any more.
+    listener.set_nonblocking(true)?;
+    thread::spawn(move || {
+        while reporter.is_listening() {
+            let stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    thread::sleep(POLL_INTERVAL);
+                    continue;
+                }
+                Err(e) => {
+                    warn!("Appearance socket {:?}: {}", path, e);
+                    thread::sleep(POLL_INTERVAL);
+                    continue;
+                }
+            };
+            // The accepted stream inherits non-blocking mode on some platforms.
+            let _ = stream.set_nonblocking(false);
+            let _ = stream.set_read_timeout(Some(SOCKET_READ_TIMEOUT));
+            let appearance = BufReader::new(stream)
+                .lines()
+                .map_while(Result::ok)
+                .filter_map(|line| Appearance::parse(&line))
+                .last();
+            match appearance {
+                Some(appearance) => {
+                    if !reporter.report(appearance) {
+                        break;
+                    }
+                }
+                None => warn!(
+                    "Appearance socket {:?}: expected \"light\" or \"dark\".",
+                    path
+                ),
+            }
+        }
+        info!("Stopped listening on appearance socket {:?}.", path);
+    });
+    Ok(())
+}
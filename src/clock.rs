@@ -0,0 +1,134 @@
+use chrono::{DateTime, Duration, Utc};
+use std::{
+    ops::RangeInclusive,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration as StdDuration, Instant},
+};
+
+// --- CLOCKS ---
+//
+// `ThemeScheduler` never reads the system time or sleeps directly. It goes
+// through a `Clock`, so tests can step through a day deterministically and the
+// demo can play a whole schedule in a minute.
+
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Blocks until `duration` of this clock's time has passed.
+    fn sleep(&self, duration: StdDuration);
+
+    /// Clock seconds per real second. Loops that want a steady *real* frame rate
+    /// scale their sleep by this.
+    fn rate(&self) -> f64 {
+        1.0
+    }
+
+    /// The real time to block for `duration` of this clock's time, or `None`
+    /// if the clock advances itself in `sleep` and nothing should block.
+    fn real_duration(&self, duration: StdDuration) -> Option<StdDuration> {
+        Some(duration.div_f64(self.rate()))
+    }
+}
+
+/// The real wall clock.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: StdDuration) {
+        thread::sleep(duration);
+    }
+}
+
+/// A clock that only moves when told to. `sleep` advances it instantly, so a
+/// scheduler driven by it runs as fast as the CPU allows and always produces
+/// the same sequence of events.
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl ManualClock {
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(start),
+        }
+    }
+
+    pub fn set(&self, instant: DateTime<Utc>) {
+        *self.now.lock().unwrap() = instant;
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: StdDuration) {
+        self.advance(Duration::from_std(duration).unwrap_or(Duration::zero()));
+    }
+
+    fn real_duration(&self, _duration: StdDuration) -> Option<StdDuration> {
+        None
+    }
+}
+
+/// The speed factors a `ScaledClock` supports. Far outside this its instants
+/// overflow within seconds, or its sleeps can't be represented.
+pub const SPEED_RANGE: RangeInclusive<f64> = 1e-3..=1e6;
+
+/// A clock that starts at `origin` and runs `factor` times faster than real
+/// time, e.g. 1440× plays a full day in one minute.
+#[derive(Debug)]
+pub struct ScaledClock {
+    origin: DateTime<Utc>,
+    started: Instant,
+    factor: f64,
+}
+
+impl ScaledClock {
+    pub fn new(origin: DateTime<Utc>, factor: f64) -> Self {
+        Self {
+            origin,
+            started: Instant::now(),
+            factor: factor.clamp(*SPEED_RANGE.start(), *SPEED_RANGE.end()),
+        }
+    }
+}
+
+impl Clock for ScaledClock {
+    fn now(&self) -> DateTime<Utc> {
+        let elapsed = self.started.elapsed().as_secs_f64() * self.factor;
+        Duration::try_milliseconds((elapsed * 1000.0) as i64)
+            .and_then(|elapsed| self.origin.checked_add_signed(elapsed))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+
+    fn sleep(&self, duration: StdDuration) {
+        thread::sleep(duration.div_f64(self.factor));
+    }
+
+    fn rate(&self) -> f64 {
+        self.factor
+    }
+}
+
+/// The clock to use for a given `--speed` factor: the system clock at 1×,
+/// otherwise a scaled clock starting now.
+pub fn clock_for_speed(speed: f64) -> Arc<dyn Clock> {
+    if speed == 1.0 {
+        Arc::new(SystemClock)
+    } else {
+        Arc::new(ScaledClock::new(Utc::now(), speed))
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     AppView,
     components::{popover::render_popover, scrollbar::render_scrollbar},
+    pairing::PairingScore,
     theme::{InterpolatableTheme, Theme},
 };
 use gpui::{
@@ -20,6 +21,7 @@ pub fn render_dropdown(
     selected_index: usize,
     preview_index: usize,
     disabled_indices: &[usize],
+    recommendations: &[PairingScore],
     disabled: bool,
     theme: &InterpolatableTheme,
     on_toggle: impl Fn(&mut AppView, &MouseDownEvent, &mut Window, &mut Context<AppView>) + 'static,
@@ -29,6 +31,7 @@ pub fn render_dropdown(
     cx: &mut Context<AppView>,
 ) -> impl IntoElement {
     let header_focus_handle = focus_handle.clone();
+    let recommended_id_prefix = "recommended-theme";
     let selected_theme_name = themes[selected_index].name.clone();
 
     let text_color = theme.0.get("text").map_or(gpui::black(), |c| c.hsla);
@@ -117,6 +120,49 @@ pub fn render_dropdown(
                                 .track_scroll(scroll_handle)
                                 .max_h_64() // Corresponds to `max-height: 16rem;` or 256px
                                 .overflow_y_scroll()
+                                .when(!recommendations.is_empty(), |list| {
+                                    list.child(
+                                        div()
+                                            .p_2()
+                                            .text_color(text_disabled_color)
+                                            .child("Recommended"),
+                                    )
+                                    .children(recommendations.iter().map(|recommendation| {
+                                        let on_select = on_select.clone();
+                                        let index = recommendation.theme_index;
+                                        let is_disabled = disabled_indices.contains(&index);
+
+                                        div()
+                                            .id((recommended_id_prefix, index))
+                                            .p_2()
+                                            .flex()
+                                            .justify_between()
+                                            .gap_2()
+                                            .when(!is_disabled, |s| {
+                                                s.hover(|style| style.bg(element_hover)).on_click(
+                                                    cx.listener(move |view, ev, win, cx| {
+                                                        on_select(index, view, ev, win, cx);
+                                                    }),
+                                                )
+                                            })
+                                            .when(is_disabled, |s| {
+                                                s.text_color(text_disabled_color)
+                                            })
+                                            .child(themes[index].name.clone())
+                                            .child(
+                                                div()
+                                                    .text_xs()
+                                                    .text_color(text_disabled_color)
+                                                    .child(recommendation.summary()),
+                                            )
+                                    }))
+                                    .child(
+                                        div()
+                                            .p_2()
+                                            .text_color(text_disabled_color)
+                                            .child("All Themes"),
+                                    )
+                                })
                                 .children(themes.iter().enumerate().map(|(index, theme_item)| {
                                     let on_select = on_select.clone();
                                     let is_disabled = disabled_indices.contains(&index);
@@ -4,4 +4,10 @@ pub mod popover;
 pub mod scrollbar;
 pub mod gradient_bar;
 pub mod form_field;
-pub mod panel;
\ No newline at end of file
+pub mod panel;
+pub mod theme_diff;
+pub mod schedule_diagnostics;
+pub mod profile_selector;
+pub mod progress_bar;
+pub mod schedule_list;
+pub mod timeline;
\ No newline at end of file
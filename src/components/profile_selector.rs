@@ -0,0 +1,53 @@
+use crate::{schedule_file::ScheduleProfile, theme::InterpolatableTheme, AppView};
+use gpui::{div, hsla, prelude::*, ClickEvent, Context, IntoElement, Window};
+
+/// A row of schedule profile names with the active one highlighted; clicking
+/// another calls `on_select` with its index.
+pub fn render_profile_selector(
+    profiles: &[ScheduleProfile],
+    active_index: usize,
+    theme: &InterpolatableTheme,
+    on_select: impl Fn(usize, &mut AppView, &ClickEvent, &mut Window, &mut Context<AppView>)
+        + 'static
+        + Clone,
+    cx: &mut Context<AppView>,
+) -> impl IntoElement {
+    let border_color = theme
+        .0
+        .get("border")
+        .map_or(hsla(0., 0., 1., 0.2), |c| c.hsla);
+    let element_hover = theme
+        .0
+        .get("element.hover")
+        .map_or(hsla(0., 0., 1., 0.1), |c| c.hsla);
+    let element_selected = theme
+        .0
+        .get("element.selected")
+        .map_or(gpui::blue(), |c| c.hsla);
+
+    div()
+        .flex()
+        .gap_2()
+        .items_center()
+        .child("Profile:")
+        .children(profiles.iter().enumerate().map(|(index, profile)| {
+            let on_select = on_select.clone();
+            let is_active = index == active_index;
+
+            div()
+                .id(("schedule-profile", index))
+                .px_2()
+                .py_1()
+                .border_1()
+                .border_color(border_color)
+                .rounded_md()
+                .when(is_active, |s| s.bg(element_selected))
+                .when(!is_active, |s| {
+                    s.hover(|style| style.bg(element_hover))
+                        .on_click(cx.listener(move |view, ev, win, cx| {
+                            on_select(index, view, ev, win, cx);
+                        }))
+                })
+                .child(profile.name.clone())
+        }))
+}
@@ -0,0 +1,19 @@
+use crate::theme::InterpolatableTheme;
+use gpui::{div, hsla, prelude::*, relative, IntoElement};
+
+/// A bar filled `progress` (0.0 to 1.0) of the way across.
+pub fn render_progress_bar(progress: f32, theme: &InterpolatableTheme) -> impl IntoElement {
+    let track_color = theme
+        .0
+        .get("element.background")
+        .map_or(hsla(0., 0., 1., 0.1), |c| c.hsla);
+    let fill_color = theme.0.get("text.accent").map_or(gpui::blue(), |c| c.hsla);
+
+    div().w_full().h_2().rounded_md().bg(track_color).child(
+        div()
+            .h_full()
+            .rounded_md()
+            .bg(fill_color)
+            .w(relative(progress.clamp(0.0, 1.0))),
+    )
+}
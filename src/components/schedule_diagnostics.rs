@@ -0,0 +1,40 @@
+use crate::{
+    theme::InterpolatableTheme,
+    validation::{Diagnostic, Severity},
+};
+use gpui::{div, hsla, prelude::*, IntoElement};
+
+/// Lists schedule problems, errors first, colored by severity.
+pub fn render_schedule_diagnostics(
+    diagnostics: &[Diagnostic],
+    theme: &InterpolatableTheme,
+) -> impl IntoElement {
+    let error_color = theme.0.get("error").map_or(gpui::red(), |c| c.hsla);
+    let warning_color = theme.0.get("warning").map_or(gpui::yellow(), |c| c.hsla);
+    let muted_color = theme
+        .0
+        .get("text.muted")
+        .map_or(hsla(0., 0., 0.5, 1.0), |c| c.hsla);
+
+    div()
+        .flex()
+        .flex_col()
+        .gap_1()
+        .text_sm()
+        .children(diagnostics.iter().map(|diagnostic| {
+            let (label, color) = match diagnostic.severity {
+                Severity::Error => ("Error", error_color),
+                Severity::Warning => ("Warning", warning_color),
+            };
+            div()
+                .flex()
+                .gap_2()
+                .child(div().text_color(color).child(label))
+                .child(
+                    div()
+                        .text_color(muted_color)
+                        .child(diagnostic.field.clone()),
+                )
+                .child(diagnostic.message.clone())
+        }))
+}
@@ -0,0 +1,59 @@
+use crate::theme::InterpolatableTheme;
+use gpui::{div, hsla, prelude::*, IntoElement};
+
+/// One occurrence of a schedule entry, as listed on the dashboard.
+pub struct ScheduleRow {
+    pub time: String,
+    pub theme_name: String,
+    /// e.g. "30m"; `None` for an entry that switches without fading.
+    pub fade: Option<String>,
+    pub is_current: bool,
+    pub is_past: bool,
+}
+
+/// Lists the day's schedule, highlighting the entry in effect and dimming
+/// those already replaced.
+pub fn render_schedule_list(rows: &[ScheduleRow], theme: &InterpolatableTheme) -> impl IntoElement {
+    let element_selected = theme
+        .0
+        .get("element.selected")
+        .map_or(gpui::blue(), |c| c.hsla);
+    let muted_color = theme
+        .0
+        .get("text.muted")
+        .map_or(hsla(0., 0., 0.5, 1.0), |c| c.hsla);
+
+    div()
+        .flex()
+        .flex_col()
+        .gap_1()
+        .text_sm()
+        .when(rows.is_empty(), |s| {
+            s.child(
+                div()
+                    .text_color(muted_color)
+                    .child("Nothing scheduled today."),
+            )
+        })
+        .children(rows.iter().map(|row| {
+            div()
+                .flex()
+                .gap_4()
+                .px_2()
+                .py_1()
+                .rounded_md()
+                .when(row.is_current, |s| s.bg(element_selected))
+                .when(row.is_past && !row.is_current, |s| {
+                    s.text_color(muted_color)
+                })
+                .child(div().w_16().child(row.time.clone()))
+                .child(div().flex_1().child(row.theme_name.clone()))
+                .when_some(row.fade.clone(), |s, fade| {
+                    s.child(
+                        div()
+                            .text_color(muted_color)
+                            .child(format!("{} fade", fade)),
+                    )
+                })
+        }))
+}
@@ -0,0 +1,88 @@
+use crate::{
+    diff::{KeyChange, ThemeDiff},
+    theme::InterpolatableTheme,
+};
+use gpui::{div, hsla, prelude::*, Hsla, IntoElement};
+
+fn render_swatch(color: Option<Hsla>, border_color: Hsla) -> impl IntoElement {
+    div()
+        .size_4()
+        .flex_shrink_0()
+        .rounded_sm()
+        .border_1()
+        .border_color(border_color)
+        .when_some(color, |s, color| s.bg(color))
+}
+
+/// Renders a scrollable list of the keys that differ between two themes, with
+/// start/end swatches and ΔE. Keys with a large hue sweep are highlighted.
+pub fn render_theme_diff(
+    id: &'static str,
+    diff: &ThemeDiff,
+    theme: &InterpolatableTheme,
+) -> impl IntoElement {
+    let border_color = theme
+        .0
+        .get("border")
+        .map_or(hsla(0., 0., 1., 0.2), |c| c.hsla);
+    let muted_color = theme
+        .0
+        .get("text.muted")
+        .map_or(hsla(0., 0., 0.5, 1.0), |c| c.hsla);
+    let warning_color = theme.0.get("warning").map_or(gpui::yellow(), |c| c.hsla);
+    let warning_background = theme
+        .0
+        .get("warning.background")
+        .map_or(hsla(0.15, 1.0, 0.5, 0.15), |c| c.hsla);
+
+    div()
+        .flex()
+        .flex_col()
+        .gap_1()
+        .child(div().text_sm().text_color(muted_color).child(format!(
+            "{} keys differ · average ΔE {:.1} · max hue sweep {:.0}°",
+            diff.entries.len(),
+            diff.average_delta_e(),
+            diff.max_hue_sweep()
+        )))
+        .child(
+            div()
+                .id(id)
+                .flex()
+                .flex_col()
+                .max_h_64()
+                .overflow_y_scroll()
+                .children(diff.entries.iter().map(|entry| {
+                    let (from, to, summary) = match entry.change {
+                        KeyChange::Added(color) => (None, Some(color.hsla), "added".to_string()),
+                        KeyChange::Removed(color) => {
+                            (Some(color.hsla), None, "removed".to_string())
+                        }
+                        KeyChange::Changed {
+                            from,
+                            to,
+                            delta_e,
+                            hue_sweep,
+                        } => (
+                            Some(from.hsla),
+                            Some(to.hsla),
+                            format!("ΔE {:.1} · {:.0}°", delta_e, hue_sweep),
+                        ),
+                    };
+
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .px_1()
+                        .text_xs()
+                        .when(entry.has_large_hue_jump(), |s| {
+                            s.bg(warning_background).text_color(warning_color)
+                        })
+                        .child(render_swatch(from, border_color))
+                        .child(render_swatch(to, border_color))
+                        .child(div().flex_1().child(entry.key.clone()))
+                        .child(summary)
+                })),
+        )
+}
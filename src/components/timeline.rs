@@ -0,0 +1,87 @@
+use crate::{theme::InterpolatableTheme, timeline::DayTimeline, AppView};
+use gpui::{div, hsla, prelude::*, px, relative, ClickEvent, Context, IntoElement, Window};
+
+/// The day as a band of colors, with a tick where each entry takes effect,
+/// shaded fade windows, and lines at `now` and the previewed moment (both as
+/// fractions of the day). Clicking a slice calls `on_select` with its index.
+pub fn render_timeline(
+    timeline: &DayTimeline,
+    now: Option<f32>,
+    preview: Option<f32>,
+    theme: &InterpolatableTheme,
+    on_select: impl Fn(usize, &mut AppView, &ClickEvent, &mut Window, &mut Context<AppView>)
+        + 'static
+        + Clone,
+    cx: &mut Context<AppView>,
+) -> impl IntoElement {
+    let border_color = theme
+        .0
+        .get("border")
+        .map_or(hsla(0., 0., 1., 0.2), |c| c.hsla);
+    let text_color = theme.0.get("text").map_or(gpui::white(), |c| c.hsla);
+    let accent_color = theme.0.get("text.accent").map_or(gpui::blue(), |c| c.hsla);
+    let muted_color = theme
+        .0
+        .get("text.muted")
+        .map_or(hsla(0., 0., 0.6, 1.), |c| c.hsla);
+
+    let line = |fraction: f32, width: f32| {
+        div()
+            .absolute()
+            .top_0()
+            .bottom_0()
+            .left(relative(fraction))
+            .w(px(width))
+    };
+
+    let band = div()
+        .relative()
+        .h_8()
+        .flex()
+        .border_1()
+        .border_color(border_color)
+        .rounded_md()
+        .overflow_hidden()
+        .children(timeline.colors.iter().enumerate().map(|(slice, color)| {
+            let on_select = on_select.clone();
+            div()
+                .id(("timeline-slice", slice))
+                .flex_1()
+                .h_full()
+                .bg(color.unwrap_or(hsla(0., 0., 0., 0.)))
+                .cursor_pointer()
+                .on_click(cx.listener(move |view, ev, win, cx| {
+                    on_select(slice, view, ev, win, cx);
+                }))
+        }))
+        .children(timeline.fades.iter().map(|&(start, end)| {
+            div()
+                .absolute()
+                .top_0()
+                .h_2()
+                .left(relative(start))
+                .w(relative(end - start))
+                .bg(muted_color.opacity(0.6))
+        }))
+        .children(
+            timeline
+                .markers
+                .iter()
+                .map(|&fraction| line(fraction, 1.).bg(text_color.opacity(0.7))),
+        )
+        .when_some(preview, |s, fraction| {
+            s.child(line(fraction, 2.).bg(text_color))
+        })
+        .when_some(now, |s, fraction| {
+            s.child(line(fraction, 2.).bg(accent_color))
+        });
+
+    div().flex().flex_col().gap_1().child(band).child(
+        div()
+            .flex()
+            .justify_between()
+            .text_xs()
+            .text_color(muted_color)
+            .children(["00:00", "06:00", "12:00", "18:00", "24:00"]),
+    )
+}
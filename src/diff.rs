@@ -0,0 +1,165 @@
+use crate::theme::{delta_e, hue_sweep_degrees, Color, InterpolatableTheme, Lab};
+
+/// Hue sweeps larger than this (in degrees) are flagged as likely to look
+/// like a rainbow rather than a fade.
+pub const LARGE_HUE_JUMP_DEGREES: f32 = 90.0;
+
+#[derive(Clone, Copy, Debug)]
+pub enum KeyChange {
+    /// The key only exists in the end theme.
+    Added(Color),
+    /// The key only exists in the start theme.
+    Removed(Color),
+    Changed {
+        from: Color,
+        to: Color,
+        delta_e: f32,
+        hue_sweep: f32,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct KeyDiff {
+    pub key: String,
+    pub change: KeyChange,
+}
+
+impl KeyDiff {
+    pub fn delta_e(&self) -> f32 {
+        match self.change {
+            KeyChange::Changed { delta_e, .. } => delta_e,
+            KeyChange::Added(_) | KeyChange::Removed(_) => 0.0,
+        }
+    }
+
+    pub fn hue_sweep(&self) -> f32 {
+        match self.change {
+            KeyChange::Changed { hue_sweep, .. } => hue_sweep,
+            KeyChange::Added(_) | KeyChange::Removed(_) => 0.0,
+        }
+    }
+
+    pub fn has_large_hue_jump(&self) -> bool {
+        self.hue_sweep() > LARGE_HUE_JUMP_DEGREES
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DiffSort {
+    #[default]
+    DeltaE,
+    HueSweep,
+    Key,
+}
+
+impl DiffSort {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::DeltaE => "ΔE",
+            Self::HueSweep => "Hue Sweep",
+            Self::Key => "Key",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Self::DeltaE => Self::HueSweep,
+            Self::HueSweep => Self::Key,
+            Self::Key => Self::DeltaE,
+        }
+    }
+}
+
+/// Every key that differs between two themes.
+#[derive(Clone, Debug, Default)]
+pub struct ThemeDiff {
+    pub entries: Vec<KeyDiff>,
+    /// Keys present in both themes, including the ones that did not change.
+    pub shared_key_count: usize,
+}
+
+impl ThemeDiff {
+    /// Compares `start` against `end`. Translucent colors are composited over
+    /// their backdrop first so ΔE reflects what is actually visible.
+    pub fn between(start: &InterpolatableTheme, end: &InterpolatableTheme) -> Self {
+        let mut entries = Vec::new();
+        let mut shared_key_count = 0;
+
+        for (key, from) in &start.0 {
+            if end.0.contains_key(key) {
+                shared_key_count += 1;
+            }
+            match end.0.get(key) {
+                Some(to) if to.rgba == from.rgba => {}
+                Some(to) => {
+                    let visible_from = from.composite_over(start.backdrop_for(key));
+                    let visible_to = to.composite_over(end.backdrop_for(key));
+                    entries.push(KeyDiff {
+                        key: key.clone(),
+                        change: KeyChange::Changed {
+                            from: *from,
+                            to: *to,
+                            delta_e: delta_e(
+                                Lab::from(visible_from.rgba),
+                                Lab::from(visible_to.rgba),
+                            ),
+                            hue_sweep: hue_sweep_degrees(*from, *to),
+                        },
+                    });
+                }
+                None => entries.push(KeyDiff {
+                    key: key.clone(),
+                    change: KeyChange::Removed(*from),
+                }),
+            }
+        }
+        for (key, to) in &end.0 {
+            if !start.0.contains_key(key) {
+                entries.push(KeyDiff {
+                    key: key.clone(),
+                    change: KeyChange::Added(*to),
+                });
+            }
+        }
+
+        let mut diff = Self {
+            entries,
+            shared_key_count,
+        };
+        diff.sort_by(DiffSort::default());
+        diff
+    }
+
+    /// Sorts largest-first for the numeric orderings, alphabetically for `Key`.
+    pub fn sort_by(&mut self, sort: DiffSort) {
+        match sort {
+            DiffSort::DeltaE => self
+                .entries
+                .sort_by(|a, b| b.delta_e().total_cmp(&a.delta_e()).then(a.key.cmp(&b.key))),
+            DiffSort::HueSweep => self.entries.sort_by(|a, b| {
+                b.hue_sweep()
+                    .total_cmp(&a.hue_sweep())
+                    .then(a.key.cmp(&b.key))
+            }),
+            DiffSort::Key => self.entries.sort_by(|a, b| a.key.cmp(&b.key)),
+        }
+    }
+
+    pub fn changed(&self) -> impl Iterator<Item = &KeyDiff> {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.change, KeyChange::Changed { .. }))
+    }
+
+    /// Mean ΔE over keys present in both themes; unchanged keys count as zero.
+    pub fn average_delta_e(&self) -> f32 {
+        if self.shared_key_count == 0 {
+            return 0.0;
+        }
+        self.changed().map(KeyDiff::delta_e).sum::<f32>() / self.shared_key_count as f32
+    }
+
+    pub fn max_hue_sweep(&self) -> f32 {
+        self.changed().map(KeyDiff::hue_sweep).fold(0.0, f32::max)
+    }
+}
@@ -0,0 +1,109 @@
+use std::{
+    process::{Command, Stdio},
+    thread,
+    time::{Duration as StdDuration, Instant},
+};
+use tracing::{info, warn};
+
+use crate::scheduler::{Transition, TransitionKind};
+
+// --- TRANSITION HOOKS ---
+//
+// Commands from the schedule file, run when a fade starts or finishes or a
+// theme is applied, so wallpapers, terminal colors and other tools can follow
+// the schedule:
+//
+//     "hooks": [
+//       { "on": "theme_applied", "command": ["sh", "-c", "set-wallpaper \"$ZED_THEME_FADER_THEME\""] }
+//     ]
+//
+// Each command gets the details in its environment:
+//
+//     ZED_THEME_FADER_EVENT       fade_started, fade_finished or theme_applied
+//     ZED_THEME_FADER_THEME       the theme being faded to, or now shown
+//     ZED_THEME_FADER_APPEARANCE  light or dark, as the theme's file says; unset
+//                                 if it doesn't
+//     ZED_THEME_FADER_PROGRESS    how far through the fade, from 0.00 to 1.00
+
+/// How long a hook may run when the file doesn't say.
+pub const DEFAULT_HOOK_TIMEOUT: StdDuration = StdDuration::from_secs(10);
+/// How often a running hook is checked for having exited.
+const HOOK_POLL_INTERVAL: StdDuration = StdDuration::from_millis(50);
+
+#[derive(Clone, Debug)]
+pub struct Hook {
+    pub on: TransitionKind,
+    /// The program and its arguments. Not run through a shell.
+    pub command: Vec<String>,
+    /// How long the command may run before it is killed.
+    pub timeout: StdDuration,
+}
+
+/// Starts every hook in `hooks` that listens for `transition`, each on its own
+/// thread so a slow command never holds up the UI. Failures are only logged.
+pub fn run_hooks(hooks: &[Hook], transition: &Transition) {
+    let mut env = vec![
+        ("ZED_THEME_FADER_EVENT", transition.kind.name().to_string()),
+        ("ZED_THEME_FADER_THEME", transition.theme.name.clone()),
+        (
+            "ZED_THEME_FADER_PROGRESS",
+            format!("{:.2}", transition.progress),
+        ),
+    ];
+    if let Some(appearance) = transition.theme.appearance {
+        env.push(("ZED_THEME_FADER_APPEARANCE", appearance.to_string()));
+    }
+    for hook in hooks.iter().filter(|hook| hook.on == transition.kind) {
+        let hook = hook.clone();
+        let env = env.clone();
+        thread::spawn(move || run_hook(&hook, &env));
+    }
+}
+
+fn run_hook(hook: &Hook, env: &[(&str, String)]) {
+    let Some((program, args)) = hook.command.split_first() else {
+        return;
+    };
+    let description = hook.command.join(" ");
+    let mut child = match Command::new(program)
+        .args(args)
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            warn!("Hook `{}` failed to start: {}", description, e);
+            return;
+        }
+    };
+
+    let deadline = Instant::now() + hook.timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => {
+                info!("Hook `{}` finished.", description);
+                return;
+            }
+            Ok(Some(status)) => {
+                warn!("Hook `{}` failed: {}", description, status);
+                return;
+            }
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                warn!(
+                    "Hook `{}` was killed after running for {:?}.",
+                    description, hook.timeout
+                );
+                return;
+            }
+            Ok(None) => thread::sleep(HOOK_POLL_INTERVAL),
+            Err(e) => {
+                warn!("Hook `{}`: {}", description, e);
+                return;
+            }
+        }
+    }
+}
@@ -1,26 +1,49 @@
-use chrono::Duration as ChronoDuration;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use gpui::{
-    div, point, prelude::*, px, Action, App, AppContext, Application, Context, Entity, FocusHandle,
-    Global, IntoElement, KeyBinding, Render, ScrollHandle, SharedString, Window,
+    div, point, prelude::*, px, Action, App, AppContext, Application, AsyncApp, Context, Entity,
+    FocusHandle, Global, IntoElement, KeyBinding, Render, ScrollHandle, SharedString, WeakEntity,
+    Window,
 };
 use schemars::JsonSchema;
 use serde::Deserialize;
-use std::fs;
+use std::{collections::HashMap, fs};
 
+pub mod appearance;
+pub mod clock;
 pub mod components;
+pub mod diff;
+pub mod hooks;
+pub mod pairing;
+pub mod schedule_editor;
+pub mod schedule_file;
 pub mod scheduler;
 pub mod simulation;
+pub mod solar;
 pub mod state;
 pub mod text_input;
 pub mod theme;
+pub mod timeline;
 pub mod ui;
-
+pub mod validation;
+pub mod zed_settings;
+
+use crate::appearance::{read_appearance_file, watch_appearance, Appearance, AppearanceSource};
+use crate::clock::SPEED_RANGE;
+use crate::diff::{DiffSort, ThemeDiff};
+use crate::schedule_editor::ScheduleEditor;
+use crate::schedule_file::{default_schedule_path, load_schedule, LoadedSchedule};
+use crate::scheduler::{CatchUp, FadeDriver, FadeStyle, OverrideUntil, SchedulerHandle};
+use crate::simulation::FrameFade;
+use crate::pairing::{recommend_end_themes, PairingScore, RECOMMENDATION_COUNT};
 use crate::state::SimulationState;
+use crate::timeline::{DayTimeline, TimelineSource, TIMELINE_KEYS};
+use crate::validation::has_errors;
+use crate::zed_settings::{read_theme_setting_file, zed_settings_path, ZedSettingsWriter};
 use text_input::{
     Backspace, Copy, Cut, Delete, End, Home, Left, Paste, Right, SelectAll, SelectLeft,
     SelectRight, TextInput,
 };
-use theme::{flatten_colors, InterpolatableTheme, Theme, ZedThemeFile};
+use theme::{flatten_colors, AlphaMode, InterpolatableTheme, Theme, ZedThemeFile};
 
 // --- 1. ACTIONS ---
 
@@ -64,6 +87,16 @@ pub struct Quit;
 #[derive(Clone, PartialEq, Action)]
 pub struct CloseWindow;
 
+/// Switches between the schedule dashboard and the interactive simulator.
+#[derive(Clone, PartialEq, Action, Deserialize, JsonSchema)]
+pub struct ToggleAppMode;
+
+/// Switches the running schedule to the named profile from the schedule file.
+#[derive(Clone, PartialEq, Action, Deserialize, JsonSchema)]
+pub struct SwitchProfile {
+    pub name: String,
+}
+
 // New enum for application mode
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum AppMode {
@@ -83,6 +116,8 @@ pub struct DropdownState {
     pub preview_index: usize,
     pub scroll_handle: ScrollHandle,
     pub focus_handle: FocusHandle,
+    // Themes listed in a "Recommended" section above the full list.
+    pub recommendations: Vec<PairingScore>,
 }
 
 impl DropdownState {
@@ -95,6 +130,7 @@ impl DropdownState {
                 .focus_handle()
                 .tab_index(tab_index as isize)
                 .tab_stop(true),
+            recommendations: Vec::new(),
         }
     }
 }
@@ -114,12 +150,65 @@ pub struct AppState {
     pub themes: Vec<Theme>,
     pub start_theme_index: usize,
     pub end_theme_index: usize,
+    // How translucent colors are blended during a fade.
+    pub alpha_mode: AlphaMode,
+    // How many times faster than real time schedules play (`--speed`).
+    pub speed: f64,
+    // Whether fades are stepped by the scheduler thread or by animation frames
+    // (`--fade-driver`).
+    pub fade_driver: FadeDriver,
+    // How a real schedule catches up after the clock jumps, e.g. on waking from
+    // suspend (`--catch-up`, in seconds; 0 jumps straight there), overriding the
+    // schedule file's `catch_up`. See `AppState::catch_up`.
+    pub catch_up_arg: Option<CatchUp>,
+    // The schedule file driving `AppMode::Scheduler`, if one was loaded.
+    pub schedule: Option<LoadedSchedule>,
+    // The desktop appearance last reported by the schedule's appearance signal.
+    pub appearance: Appearance,
+    // The appearance the OS reports for our window, for when the schedule has
+    // no appearance signal.
+    pub system_appearance: Appearance,
+    // Keeps Zed's own theme setting in step with the scheduler, if enabled
+    // (`--write-zed-settings`).
+    pub zed_settings: Option<ZedSettingsWriter>,
     // The currently active theme, which the UI renders.
     pub active_theme: InterpolatableTheme,
 }
 
 impl Global for AppState {}
 
+impl AppState {
+    /// How schedules catch up after the clock jumps: as `--catch-up` says, else
+    /// as the schedule file says, else a short fade.
+    pub fn catch_up(&self) -> CatchUp {
+        self.catch_up_arg
+            .or_else(|| self.schedule.as_ref()?.catch_up)
+            .unwrap_or_default()
+    }
+
+    /// How the simulator blends its fades.
+    pub fn simulation_fade_style(&self) -> FadeStyle {
+        FadeStyle {
+            alpha_mode: self.alpha_mode,
+            ..FadeStyle::default()
+        }
+    }
+
+    /// Whether the desktop is light or dark: as the schedule's appearance signal
+    /// last reported, or as the OS reports it when no signal is configured.
+    pub fn desktop_appearance(&self) -> Appearance {
+        let has_signal = self
+            .schedule
+            .as_ref()
+            .is_some_and(|schedule| !schedule.appearance_sources.is_empty());
+        if has_signal {
+            self.appearance
+        } else {
+            self.system_appearance
+        }
+    }
+}
+
 // --- 5. THE MAIN UI VIEW (REFACTORED) ---
 
 pub struct AppView {
@@ -127,9 +216,43 @@ pub struct AppView {
     pub end_dropdown_state: DropdownState,
     pub sleep_input_state: ValidatedInputState,
     pub fade_input_state: ValidatedInputState,
+    pub alpha_mode_focus_handle: FocusHandle,
     pub run_simulation_focus_handle: FocusHandle,
     pub root_focus_handle: FocusHandle,
     pub simulation_state: SimulationState,
+    // Controls the running simulation. Dropping it (including when this view is
+    // closed) cancels the scheduler thread.
+    pub scheduler_handle: Option<SchedulerHandle>,
+    // The fade being animated on render, when using `FadeDriver::Frames`.
+    pub frame_fade: Option<FrameFade>,
+    pub pause_focus_handle: FocusHandle,
+    pub cancel_focus_handle: FocusHandle,
+    pub show_theme_diff: bool,
+    pub diff_sort: DiffSort,
+    // The diff last shown, and the start theme, end theme and sort it was built for.
+    pub theme_diff: Option<((usize, usize, DiffSort), ThemeDiff)>,
+    pub diff_toggle_focus_handle: FocusHandle,
+    // The end themes ranked so far for each start theme, and the fade style they
+    // were scored for.
+    pub end_recommendations: (FadeStyle, HashMap<usize, Vec<PairingScore>>),
+    pub diff_sort_focus_handle: FocusHandle,
+    // How long a theme picked while a schedule runs holds before the schedule
+    // takes over again.
+    pub override_until: OverrideUntil,
+    pub override_until_focus_handle: FocusHandle,
+    pub skip_focus_handle: FocusHandle,
+    pub app_mode_focus_handle: FocusHandle,
+    // The dashboard timeline: which `TIMELINE_KEYS` entry colors it, the day
+    // last sampled, and the moment clicked to preview, if any.
+    pub timeline_key: usize,
+    pub timeline_key_focus_handle: FocusHandle,
+    pub timeline: Option<(TimelineSource, DayTimeline)>,
+    pub timeline_preview: Option<DateTime<Utc>>,
+    // The schedule file being edited in the dashboard, if the editor is open.
+    pub schedule_editor: Option<ScheduleEditor>,
+    pub edit_schedule_focus_handle: FocusHandle,
+    // Why the themes Zed is set to couldn't be preselected, until dismissed.
+    pub zed_settings_notice: Option<SharedString>,
 }
 
 impl AppView {
@@ -138,11 +261,13 @@ impl AppView {
         sleep_input: Entity<TextInput>,
         fade_input: Entity<TextInput>,
     ) -> Self {
-        let end_theme_index = cx.global::<AppState>().themes.len().saturating_sub(1);
+        let app_state = cx.global::<AppState>();
+        let (start_theme_index, end_theme_index) =
+            (app_state.start_theme_index, app_state.end_theme_index);
         let root_focus_handle = cx.focus_handle();
 
-        Self {
-            start_dropdown_state: DropdownState::new(0, 1, cx),
+        let mut view = Self {
+            start_dropdown_state: DropdownState::new(start_theme_index, 1, cx),
             end_dropdown_state: DropdownState::new(end_theme_index, 2, cx),
             sleep_input_state: ValidatedInputState {
                 input: sleep_input,
@@ -152,10 +277,81 @@ impl AppView {
                 input: fade_input,
                 validation_message: None,
             },
-            run_simulation_focus_handle: cx.focus_handle().tab_index(5).tab_stop(true),
+            alpha_mode_focus_handle: cx.focus_handle().tab_index(5).tab_stop(true),
+            run_simulation_focus_handle: cx.focus_handle().tab_index(6).tab_stop(true),
             root_focus_handle,
             simulation_state: SimulationState::Idle,
+            scheduler_handle: None,
+            frame_fade: None,
+            pause_focus_handle: cx.focus_handle().tab_index(7).tab_stop(true),
+            cancel_focus_handle: cx.focus_handle().tab_index(8).tab_stop(true),
+            show_theme_diff: false,
+            diff_sort: DiffSort::default(),
+            theme_diff: None,
+            end_recommendations: (FadeStyle::default(), HashMap::new()),
+            diff_toggle_focus_handle: cx.focus_handle().tab_index(9).tab_stop(true),
+            diff_sort_focus_handle: cx.focus_handle().tab_index(10).tab_stop(true),
+            override_until: OverrideUntil::NextEvent,
+            override_until_focus_handle: cx.focus_handle().tab_index(11).tab_stop(true),
+            skip_focus_handle: cx.focus_handle().tab_index(12).tab_stop(true),
+            app_mode_focus_handle: cx.focus_handle().tab_index(13).tab_stop(true),
+            timeline_key: 0,
+            timeline_key_focus_handle: cx.focus_handle().tab_index(14).tab_stop(true),
+            timeline: None,
+            timeline_preview: None,
+            schedule_editor: None,
+            edit_schedule_focus_handle: cx.focus_handle().tab_index(15).tab_stop(true),
+            zed_settings_notice: None,
+        };
+        view.refresh_end_recommendations(cx);
+        view
+    }
+
+    /// Shows the end themes ranked against the current start theme. Ranking
+    /// scores every loaded theme, so it runs on the background executor the
+    /// first time each start theme is picked and is cached from then on.
+    pub fn refresh_end_recommendations(&mut self, cx: &mut Context<Self>) {
+        let app_state = cx.global::<AppState>();
+        let (start_index, style) = (
+            app_state.start_theme_index,
+            app_state.simulation_fade_style(),
+        );
+        let (scored_style, scores) = &mut self.end_recommendations;
+        if *scored_style != style {
+            *scored_style = style;
+            scores.clear();
         }
+        if let Some(recommendations) = scores.get(&start_index) {
+            self.end_dropdown_state.recommendations = recommendations.clone();
+            return;
+        }
+        self.end_dropdown_state.recommendations.clear();
+
+        let themes = app_state.themes.clone();
+        cx.spawn(move |view: WeakEntity<AppView>, cx: &mut AsyncApp| {
+            let mut cx = cx.clone();
+            async move {
+                let recommendations = cx
+                    .background_executor()
+                    .spawn(async move {
+                        let mut recommendations = recommend_end_themes(&themes, start_index, style);
+                        recommendations.truncate(RECOMMENDATION_COUNT);
+                        recommendations
+                    })
+                    .await;
+                let _ = view.update(&mut cx, |view, cx| {
+                    // Scores for a fade style that has since changed are stale.
+                    let (scored_style, scores) = &mut view.end_recommendations;
+                    if *scored_style != style {
+                        return;
+                    }
+                    scores.insert(start_index, recommendations);
+                    view.refresh_end_recommendations(cx);
+                    cx.notify();
+                });
+            }
+        })
+        .detach();
     }
 
     fn on_focus_next(&mut self, _: &FocusNext, window: &mut Window, _: &mut Context<Self>) {
@@ -193,18 +389,42 @@ impl AppView {
     pub fn close_dropdowns(&mut self, cx: &mut Context<Self>) {
         self.start_dropdown_state.is_open = false;
         self.end_dropdown_state.is_open = false;
+        if let Some(editor) = &mut self.schedule_editor {
+            for row in &mut editor.rows {
+                row.theme_dropdown.is_open = false;
+            }
+        }
         cx.notify();
     }
 
+    /// The schedule editor's open theme dropdown, and the row it belongs to.
+    fn open_editor_dropdown(&mut self) -> Option<(usize, &mut DropdownState)> {
+        self.schedule_editor
+            .as_mut()?
+            .rows
+            .iter_mut()
+            .enumerate()
+            .find(|(_, row)| row.theme_dropdown.is_open)
+            .map(|(index, row)| (index, &mut row.theme_dropdown))
+    }
+
     pub fn select_start_theme(&mut self, index: usize, cx: &mut Context<Self>) {
         cx.update_global::<AppState, _>(|app_state, _| {
-            if index != app_state.end_theme_index {
+            if Some(index) != start_disabled_index(app_state) {
                 app_state.start_theme_index = index;
                 // Instantly update the active theme
                 let theme = &app_state.themes[index].interpolatable_theme;
                 app_state.active_theme = theme.clone();
             }
         });
+        // While a schedule runs, the pick overrides it until `override_until`.
+        if let Some(handle) = &self.scheduler_handle {
+            let app_state = cx.global::<AppState>();
+            if app_state.app_mode == AppMode::Scheduler {
+                handle.override_theme(&app_state.themes[index], self.override_until);
+            }
+        }
+        self.refresh_end_recommendations(cx);
         self.start_dropdown_state.is_open = false;
         cx.notify();
     }
@@ -219,6 +439,177 @@ impl AppView {
         cx.notify();
     }
 
+    pub fn cycle_alpha_mode(&mut self, cx: &mut Context<Self>) {
+        cx.update_global::<AppState, _>(|app_state, _| {
+            app_state.alpha_mode = app_state.alpha_mode.next();
+        });
+        // Fades now blend differently, so the end themes are ranked again.
+        self.refresh_end_recommendations(cx);
+        cx.notify();
+    }
+
+    pub fn cycle_override_until(&mut self, cx: &mut Context<Self>) {
+        self.override_until = self.override_until.next();
+        cx.notify();
+    }
+
+    pub fn cycle_timeline_key(&mut self, cx: &mut Context<Self>) {
+        self.timeline_key = (self.timeline_key + 1) % TIMELINE_KEYS.len();
+        cx.notify();
+    }
+
+    /// Shows the schedule's theme at the middle of timeline slice `slice`, or
+    /// returns to the live theme if that moment is already being previewed.
+    pub fn preview_timeline_slice(&mut self, slice: usize, cx: &mut Context<Self>) {
+        let Some((_, timeline)) = &self.timeline else {
+            return;
+        };
+        let instant = timeline.slice_instant(slice);
+        self.timeline_preview = (self.timeline_preview != Some(instant)).then_some(instant);
+        cx.notify();
+    }
+
+    pub fn clear_timeline_preview(&mut self, cx: &mut Context<Self>) {
+        self.timeline_preview = None;
+        cx.notify();
+    }
+
+    pub fn dismiss_zed_settings_notice(&mut self, cx: &mut Context<Self>) {
+        self.zed_settings_notice = None;
+        cx.notify();
+    }
+
+    /// Opens the schedule editor on the loaded schedule file's active profile,
+    /// or on a new file at the default path, or closes it.
+    pub fn toggle_schedule_editor(&mut self, cx: &mut Context<Self>) {
+        if self.schedule_editor.take().is_none() {
+            let schedule = cx.global::<AppState>().schedule.as_ref();
+            let profile_name = schedule.map(|schedule| schedule.active().name.clone());
+            let Some(path) = schedule
+                .map(|schedule| schedule.path.clone())
+                .or_else(default_schedule_path)
+            else {
+                // The button that opens the editor is disabled in this case.
+                return;
+            };
+            self.schedule_editor = Some(ScheduleEditor::open(path, profile_name.as_deref(), cx));
+        }
+        self.close_dropdowns(cx);
+    }
+
+    /// Called whenever one of the editor's text inputs changes.
+    pub fn validate_schedule_editor(&mut self, cx: &mut Context<Self>) {
+        let Some(editor) = &mut self.schedule_editor else {
+            return;
+        };
+        if editor.revalidate(&cx.global::<AppState>().themes, cx) {
+            cx.notify();
+        }
+    }
+
+    pub fn toggle_editor_dropdown(&mut self, row: usize, cx: &mut Context<Self>) {
+        let Some(is_open) = self
+            .schedule_editor
+            .as_ref()
+            .and_then(|editor| editor.rows.get(row))
+            .map(|row| !row.theme_dropdown.is_open)
+        else {
+            return;
+        };
+        self.close_dropdowns(cx);
+        if let Some(row) = self
+            .schedule_editor
+            .as_mut()
+            .and_then(|editor| editor.rows.get_mut(row))
+        {
+            row.theme_dropdown.is_open = is_open;
+        }
+    }
+
+    pub fn select_editor_theme(&mut self, row: usize, theme_index: usize, cx: &mut Context<Self>) {
+        if let Some(editor) = &mut self.schedule_editor {
+            editor.set_theme(row, theme_index, &cx.global::<AppState>().themes, cx);
+        }
+        cx.notify();
+    }
+
+    pub fn add_editor_row(&mut self, cx: &mut Context<Self>) {
+        if let Some(editor) = &mut self.schedule_editor {
+            editor.add_row(cx);
+        }
+        cx.notify();
+    }
+
+    pub fn remove_editor_row(&mut self, row: usize, cx: &mut Context<Self>) {
+        if let Some(editor) = &mut self.schedule_editor {
+            editor.remove_row(row, &cx.global::<AppState>().themes, cx);
+        }
+        cx.notify();
+    }
+
+    pub fn move_editor_row(&mut self, row: usize, up: bool, cx: &mut Context<Self>) {
+        if let Some(editor) = &mut self.schedule_editor {
+            editor.move_row(row, up, &cx.global::<AppState>().themes, cx);
+        }
+        cx.notify();
+    }
+
+    pub fn cycle_editor_easing(&mut self, row: usize, cx: &mut Context<Self>) {
+        if let Some(editor) = &mut self.schedule_editor {
+            editor.cycle_easing(row);
+        }
+        cx.notify();
+    }
+
+    pub fn reload_schedule_editor(&mut self, cx: &mut Context<Self>) {
+        if let Some(editor) = &mut self.schedule_editor {
+            if let Err(e) = editor.reload(cx) {
+                editor.status = Some(format!("{:#}", e).into());
+            }
+        }
+        cx.notify();
+    }
+
+    /// Saves the editor's schedule and runs it in place of the loaded one.
+    pub fn save_schedule_editor(&mut self, cx: &mut Context<Self>) {
+        let Some(editor) = &mut self.schedule_editor else {
+            return;
+        };
+        let loaded = match editor.save(&cx.global::<AppState>().themes, cx) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                editor.status = Some(format!("{:#}", e).into());
+                cx.notify();
+                return;
+            }
+        };
+        editor.status = Some(format!("Saved to {}.", editor.path.display()).into());
+
+        let active_profile = cx.update_global::<AppState, _>(|app_state, _| {
+            let active_name = app_state
+                .schedule
+                .as_ref()
+                .map(|schedule| schedule.active().name.clone());
+            let active_profile = active_name
+                .and_then(|name| loaded.profile_index(&name))
+                .unwrap_or(loaded.active_profile);
+            app_state.schedule = Some(loaded);
+            active_profile
+        });
+        self.timeline = None;
+        self.switch_profile(active_profile, cx);
+    }
+
+    pub fn toggle_theme_diff(&mut self, cx: &mut Context<Self>) {
+        self.show_theme_diff = !self.show_theme_diff;
+        cx.notify();
+    }
+
+    pub fn cycle_diff_sort(&mut self, cx: &mut Context<Self>) {
+        self.diff_sort = self.diff_sort.next();
+        cx.notify();
+    }
+
     fn scroll_dropdown_to_preview_index(window: &Window, dropdown_state: &mut DropdownState) {
         let rem_size = window.rem_size();
         let item_height = window.line_height() + rem_size;
@@ -226,7 +617,13 @@ impl AppView {
         let container_bounds = scroll_handle.bounds();
         if container_bounds.size.height > px(0.0) {
             let current_offset = scroll_handle.offset().y;
-            let item_top = item_height * dropdown_state.preview_index as f32;
+            // The "Recommended" section adds its items plus two header rows above the full list.
+            let leading_rows = if dropdown_state.recommendations.is_empty() {
+                0
+            } else {
+                dropdown_state.recommendations.len() + 2
+            };
+            let item_top = item_height * (leading_rows + dropdown_state.preview_index) as f32;
             let item_bottom = item_top + item_height;
             let visible_top = -current_offset;
             let visible_bottom = visible_top + container_bounds.size.height;
@@ -249,9 +646,17 @@ impl AppView {
         }
 
         let (dropdown_state, disabled_index) = if self.start_dropdown_state.is_open {
-            (&mut self.start_dropdown_state, app_state.end_theme_index)
+            (
+                &mut self.start_dropdown_state,
+                start_disabled_index(app_state),
+            )
         } else if self.end_dropdown_state.is_open {
-            (&mut self.end_dropdown_state, app_state.start_theme_index)
+            (
+                &mut self.end_dropdown_state,
+                Some(app_state.start_theme_index),
+            )
+        } else if let Some((_, dropdown_state)) = self.open_editor_dropdown() {
+            (dropdown_state, None)
         } else {
             // If dropdowns were closed, check focus and open the correct one.
             if self.start_dropdown_state.focus_handle.is_focused(window) {
@@ -266,7 +671,7 @@ impl AppView {
         let mut current_index = dropdown_state.preview_index;
         while current_index < theme_count - 1 {
             current_index += 1;
-            if current_index != disabled_index {
+            if Some(current_index) != disabled_index {
                 dropdown_state.preview_index = current_index;
                 break;
             }
@@ -285,9 +690,17 @@ impl AppView {
         }
 
         let (dropdown_state, disabled_index) = if self.start_dropdown_state.is_open {
-            (&mut self.start_dropdown_state, app_state.end_theme_index)
+            (
+                &mut self.start_dropdown_state,
+                start_disabled_index(app_state),
+            )
         } else if self.end_dropdown_state.is_open {
-            (&mut self.end_dropdown_state, app_state.start_theme_index)
+            (
+                &mut self.end_dropdown_state,
+                Some(app_state.start_theme_index),
+            )
+        } else if let Some((_, dropdown_state)) = self.open_editor_dropdown() {
+            (dropdown_state, None)
         } else {
             // If dropdowns were closed, check focus and open the correct one.
             if self.start_dropdown_state.focus_handle.is_focused(window) {
@@ -302,7 +715,7 @@ impl AppView {
         let mut current_index = dropdown_state.preview_index;
         while current_index > 0 {
             current_index -= 1;
-            if current_index != disabled_index {
+            if Some(current_index) != disabled_index {
                 dropdown_state.preview_index = current_index;
                 break;
             }
@@ -338,6 +751,9 @@ impl AppView {
             self.select_start_theme(self.start_dropdown_state.preview_index, cx);
         } else if self.end_dropdown_state.is_open {
             self.select_end_theme(self.end_dropdown_state.preview_index, cx);
+        } else if let Some((row, dropdown_state)) = self.open_editor_dropdown() {
+            let theme_index = dropdown_state.preview_index;
+            self.select_editor_theme(row, theme_index, cx);
         } else if self.start_dropdown_state.focus_handle.is_focused(window) {
             self.toggle_start_dropdown(cx);
         } else if self.end_dropdown_state.focus_handle.is_focused(window) {
@@ -396,12 +812,8 @@ impl AppView {
         {
             let (start_theme, end_theme) = cx.read_global(|app_state: &AppState, _| {
                 (
-                    app_state.themes[app_state.start_theme_index]
-                        .interpolatable_theme
-                        .clone(),
-                    app_state.themes[app_state.end_theme_index]
-                        .interpolatable_theme
-                        .clone(),
+                    app_state.themes[app_state.start_theme_index].clone(),
+                    app_state.themes[app_state.end_theme_index].clone(),
                 )
             });
 
@@ -410,36 +822,203 @@ impl AppView {
             let sleep_duration = ChronoDuration::seconds(sleep as i64);
             let fade_duration = ChronoDuration::seconds(fade as i64);
 
-            // Get theme names for logging
-            let (start_theme_name, end_theme_name) = cx.read_global(|app_state: &AppState, _| {
-                (
-                    app_state.themes[app_state.start_theme_index].name.clone(),
-                    app_state.themes[app_state.end_theme_index].name.clone(),
-                )
-            });
-
-            simulation::run_simulation_core(
+            self.scheduler_handle = Some(simulation::run_simulation_core(
                 cx,
                 start_theme,
                 end_theme,
                 sleep_duration,
                 fade_duration,
-                start_theme_name.into(),
-                end_theme_name.into(),
-            );
+            ));
+        }
+
+        cx.notify();
+    }
+
+    /// Makes profile `index` of the schedule file the one being run. The running
+    /// scheduler fades into it without restarting; a profile with errors stops
+    /// the scheduler and shows them instead.
+    pub fn switch_profile(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some((profile, appearance, app_mode)) =
+            cx.update_global::<AppState, _>(|app_state, _| {
+                let schedule = app_state.schedule.as_mut()?;
+                schedule.active_profile = index;
+                Some((
+                    schedule.active().clone(),
+                    app_state.appearance,
+                    app_state.app_mode,
+                ))
+            })
+        else {
+            return;
+        };
+
+        if app_mode == AppMode::Interactive {
+            // The profile is started on switching back to the scheduler.
+            return;
+        }
+        if has_errors(&profile.diagnostics) {
+            self.scheduler_handle = None;
+            self.frame_fade = None;
+            self.simulation_state = SimulationState::Idle;
+        } else if let Some(handle) = &self.scheduler_handle {
+            handle.set_schedule(profile.schedule_for(appearance));
+        } else {
+            self.start_schedule(cx);
+        }
+        cx.notify();
+    }
+
+    /// Runs the schedule file's active profile, unless it has errors.
+    pub fn start_schedule(&mut self, cx: &mut Context<Self>) {
+        let app_state = cx.global::<AppState>();
+        let Some(profile) = app_state.schedule.as_ref().map(LoadedSchedule::active) else {
+            return;
+        };
+        if has_errors(&profile.diagnostics) {
+            return;
+        }
+        let entries = profile.schedule_for(app_state.appearance);
+        self.scheduler_handle = Some(simulation::run_schedule_core(cx, entries));
+    }
+
+    /// Switches between `AppMode::Scheduler` and `AppMode::Interactive`. Both
+    /// drive the theme through `scheduler_handle`, so whatever is running stops.
+    pub fn toggle_app_mode(&mut self, cx: &mut Context<Self>) {
+        self.scheduler_handle = None;
+        self.frame_fade = None;
+        self.simulation_state = SimulationState::Idle;
+        self.timeline_preview = None;
+        self.close_dropdowns(cx);
+        let app_mode = cx.update_global::<AppState, _>(|app_state, _| {
+            app_state.app_mode = match app_state.app_mode {
+                AppMode::Scheduler => AppMode::Interactive,
+                AppMode::Interactive => AppMode::Scheduler,
+            };
+            app_state.active_theme = app_state.themes[app_state.start_theme_index]
+                .interpolatable_theme
+                .clone();
+            app_state.app_mode
+        });
+        if app_mode == AppMode::Scheduler {
+            self.start_schedule(cx);
+        }
+        cx.notify();
+    }
+
+    fn on_toggle_app_mode(
+        &mut self,
+        _: &ToggleAppMode,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_app_mode(cx);
+    }
+
+    pub fn skip_to_next(&mut self, cx: &mut Context<Self>) {
+        if let Some(handle) = &self.scheduler_handle {
+            handle.skip_to_next();
+        }
+        cx.notify();
+    }
+
+    /// Records a new desktop appearance, fading to its theme if the active
+    /// profile follows the appearance.
+    pub fn set_appearance(&mut self, appearance: Appearance, cx: &mut Context<Self>) {
+        let config = cx.update_global::<AppState, _>(|app_state, _| {
+            app_state.appearance = appearance;
+            app_state
+                .schedule
+                .as_ref()
+                .and_then(|schedule| schedule.active().appearance.clone())
+        });
+        if let (Some(handle), Some(config)) = (&self.scheduler_handle, config) {
+            handle.set_schedule_with_fade(config.schedule(appearance), config.fade_duration);
+        }
+    }
+
+    fn on_switch_profile(
+        &mut self,
+        action: &SwitchProfile,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let index = cx
+            .global::<AppState>()
+            .schedule
+            .as_ref()
+            .and_then(|schedule| schedule.profile_index(&action.name));
+        match index {
+            Some(index) => self.switch_profile(index, cx),
+            None => eprintln!("No schedule profile is named '{}'", action.name),
         }
+    }
 
+    pub fn toggle_pause_simulation(&mut self, cx: &mut Context<Self>) {
+        let Some(handle) = &self.scheduler_handle else {
+            return;
+        };
+        if self.simulation_state == SimulationState::Paused {
+            handle.resume();
+        } else {
+            handle.pause();
+        }
+        cx.notify();
+    }
+
+    pub fn cancel_simulation(&mut self, cx: &mut Context<Self>) {
+        if let Some(handle) = self.scheduler_handle.take() {
+            handle.cancel();
+        }
+        // Undo any partial fade.
+        self.frame_fade = None;
+        cx.update_global::<AppState, _>(|app_state, _| {
+            app_state.active_theme = app_state.themes[app_state.start_theme_index]
+                .interpolatable_theme
+                .clone();
+        });
+        self.simulation_state = SimulationState::Idle;
         cx.notify();
     }
 
     fn render_interactive_ui(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         ui::render_interactive_ui(self, window, cx)
     }
+
+    fn render_scheduler_ui(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        ui::render_scheduler_ui(self, window, cx)
+    }
+}
+
+/// The start theme can't also be the end theme, except in `AppMode::Scheduler`,
+/// where the start dropdown picks a theme to override the schedule with.
+fn start_disabled_index(app_state: &AppState) -> Option<usize> {
+    match app_state.app_mode {
+        AppMode::Scheduler => None,
+        AppMode::Interactive => Some(app_state.end_theme_index),
+    }
 }
 
 impl Render for AppView {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         // println!("AppView::render called");
+        // Step a frame-driven fade. Frames are only requested while the window
+        // is being drawn, so a hidden window stops interpolating.
+        if let Some(fade) = &self.frame_fade {
+            if self.simulation_state != SimulationState::Paused {
+                let (progress, theme) = fade.sample();
+                cx.update_global::<AppState, _>(|app_state, _| {
+                    app_state.active_theme = theme;
+                });
+                self.simulation_state = SimulationState::Fading { progress };
+                if progress < 1.0 {
+                    window.request_animation_frame();
+                } else {
+                    // The end theme stays until the scheduler's own `ThemeUpdate`,
+                    // without re-interpolating on every render.
+                    self.frame_fade = None;
+                }
+            }
+        }
         let app_state = cx.global::<AppState>().clone();
 
         // Logic to close dropdowns if they lose focus
@@ -461,36 +1040,15 @@ impl Render for AppView {
         }
 
         let content = match app_state.app_mode {
-            AppMode::Scheduler => div()
-                .flex()
-                .size_full()
-                .justify_center()
-                .items_center()
-                .bg(app_state
-                    .active_theme
-                    .0
-                    .get("surface.background")
-                    .expect("Theme missing surface.background")
-                    .hsla)
-                .text_color(
-                    app_state
-                        .active_theme
-                        .0
-                        .get("text")
-                        .expect("Theme missing text color")
-                        .hsla,
-                )
-                .child(format!(
-                    "Current Theme: {}",
-                    app_state.themes[app_state.start_theme_index].name
-                ))
-                .into_any_element(),
+            AppMode::Scheduler => self.render_scheduler_ui(window, cx).into_any_element(),
             AppMode::Interactive => self.render_interactive_ui(window, cx).into_any_element(),
         };
 
         div()
             .size_full()
             .on_action(cx.listener(Self::on_close_window))
+            .on_action(cx.listener(Self::on_switch_profile))
+            .on_action(cx.listener(Self::on_toggle_app_mode))
             .child(content)
     }
 }
@@ -522,6 +1080,10 @@ fn load_themes_from_dir(path: &std::path::Path) -> Vec<Theme> {
                             flatten_colors(&theme_def.style.colors, &mut interpolatable_theme, "");
                             Theme {
                                 name: theme_def.name,
+                                appearance: theme_def
+                                    .appearance
+                                    .as_deref()
+                                    .and_then(Appearance::parse),
                                 interpolatable_theme,
                             }
                         })
@@ -540,11 +1102,161 @@ fn load_themes_from_dir(path: &std::path::Path) -> Vec<Theme> {
         .collect()
 }
 
+/// Reads `--speed <factor>` (or `--speed=<factor>`) from the command line,
+/// e.g. `--speed 1440` plays a full day's schedule in one minute.
+fn parse_speed_arg() -> f64 {
+    let args: Vec<String> = std::env::args().collect();
+    let value = args.iter().enumerate().find_map(|(i, arg)| {
+        if arg == "--speed" {
+            args.get(i + 1).cloned()
+        } else {
+            arg.strip_prefix("--speed=").map(str::to_string)
+        }
+    });
+    match value.map(|v| v.parse::<f64>()) {
+        Some(Ok(speed)) if SPEED_RANGE.contains(&speed) => speed,
+        Some(_) => {
+            eprintln!(
+                "Ignoring invalid --speed value; it must be a number from {} to {}.",
+                SPEED_RANGE.start(),
+                SPEED_RANGE.end()
+            );
+            1.0
+        }
+        None => 1.0,
+    }
+}
+
+fn parse_fade_driver_arg() -> FadeDriver {
+    let args: Vec<String> = std::env::args().collect();
+    let value = args.iter().enumerate().find_map(|(i, arg)| {
+        if arg == "--fade-driver" {
+            args.get(i + 1).cloned()
+        } else {
+            arg.strip_prefix("--fade-driver=").map(str::to_string)
+        }
+    });
+    match value.map(|v| FadeDriver::parse(&v)) {
+        Some(Some(driver)) => driver,
+        Some(None) => {
+            eprintln!("Ignoring invalid --fade-driver value; use \"thread\" or \"frames\".");
+            FadeDriver::default()
+        }
+        None => FadeDriver::default(),
+    }
+}
+
+fn parse_catch_up_arg() -> Option<CatchUp> {
+    let args: Vec<String> = std::env::args().collect();
+    let value = args.iter().enumerate().find_map(|(i, arg)| {
+        if arg == "--catch-up" {
+            args.get(i + 1).cloned()
+        } else {
+            arg.strip_prefix("--catch-up=").map(str::to_string)
+        }
+    });
+    match value.map(|v| v.parse::<f64>()) {
+        Some(Ok(seconds)) if seconds == 0.0 => Some(CatchUp::Jump),
+        Some(Ok(seconds)) if seconds > 0.0 => Some(CatchUp::Fade(ChronoDuration::milliseconds(
+            (seconds * 1000.0) as i64,
+        ))),
+        Some(_) => {
+            eprintln!("Ignoring invalid --catch-up value; it must be a number of seconds.");
+            None
+        }
+        None => None,
+    }
+}
+
+/// Reads `--schedule <path>`, falling back to the default schedule file if it exists.
+fn parse_schedule_arg() -> Option<std::path::PathBuf> {
+    let args: Vec<String> = std::env::args().collect();
+    let value = args.iter().enumerate().find_map(|(i, arg)| {
+        if arg == "--schedule" {
+            args.get(i + 1).cloned()
+        } else {
+            arg.strip_prefix("--schedule=").map(str::to_string)
+        }
+    });
+    value
+        .map(std::path::PathBuf::from)
+        .or_else(|| default_schedule_path().filter(|path| path.exists()))
+}
+
+/// Reads `--write-zed-settings` (Zed's own settings file) or
+/// `--write-zed-settings=<path>` (another one).
+fn parse_write_zed_settings_arg() -> Option<ZedSettingsWriter> {
+    let path = std::env::args().find_map(|arg| {
+        if arg == "--write-zed-settings" {
+            zed_settings_path()
+        } else {
+            arg.strip_prefix("--write-zed-settings=")
+                .map(std::path::PathBuf::from)
+        }
+    })?;
+    Some(ZedSettingsWriter::new(path))
+}
+
+/// The start and end themes to open with: the theme Zed is showing, and the
+/// other half of its light/dark pair. Falls back to the first two themes, with
+/// a notice naming whatever couldn't be found.
+fn initial_theme_indices(
+    themes: &[Theme],
+    appearance: Appearance,
+) -> (usize, usize, Option<SharedString>) {
+    let default_end = if themes.len() > 1 { 1 } else { 0 };
+    let setting = match zed_settings_path().map(|path| read_theme_setting_file(&path)) {
+        Some(Ok(Some(setting))) => setting,
+        Some(Ok(None)) | None => return (0, default_end, None),
+        Some(Err(e)) => {
+            eprintln!("{:#}", e);
+            return (0, default_end, Some(format!("{:#}", e).into()));
+        }
+    };
+
+    let mut missing = Vec::new();
+    let mut resolve = |name: Option<&str>| {
+        let name = name?;
+        let index = themes.iter().position(|theme| theme.name == name);
+        if index.is_none() {
+            missing.push(format!("\"{}\"", name));
+        }
+        index
+    };
+    let (shown, other) = setting.shown_and_other(appearance);
+    let start = resolve(shown);
+    let end = resolve(other).filter(|&end| Some(end) != start);
+
+    let start = start.unwrap_or(0);
+    // The end theme can't also be the start theme.
+    let end = end.unwrap_or(if start == 0 { default_end } else { 0 });
+    let notice = (!missing.is_empty()).then(|| {
+        let notice = format!(
+            "Zed's settings name {}, which {} among the loaded themes.",
+            missing.join(" and "),
+            if missing.len() == 1 {
+                "isn't"
+            } else {
+                "aren't"
+            }
+        );
+        eprintln!("{}", notice);
+        notice.into()
+    });
+    (start, end, notice)
+}
+
 fn main() {
     tracing_subscriber::fmt()
         .with_max_level(tracing::Level::INFO)
         .init();
 
+    let speed = parse_speed_arg();
+    let fade_driver = parse_fade_driver_arg();
+    let catch_up_arg = parse_catch_up_arg();
+    let schedule_path = parse_schedule_arg();
+    let zed_settings = parse_write_zed_settings_arg();
+
     // --- Parse themes ---
     let mut all_themes = load_themes_from_dir(std::path::Path::new("assets/"));
 
@@ -582,26 +1294,70 @@ fn main() {
             KeyBinding::new("enter", Submit, Some("RunButton")),
             KeyBinding::new("cmd-q", Quit, None),
             KeyBinding::new("cmd-w", CloseWindow, None),
+            KeyBinding::new("cmd-shift-m", ToggleAppMode, None),
+            KeyBinding::new("tab", FocusNext, Some("SchedulerUI")),
+            KeyBinding::new("shift-tab", FocusPrev, Some("SchedulerUI")),
         ]);
 
         cx.on_action(|_: &Quit, cx| cx.quit());
 
         // --- Initialize AppState ---
-        let app_mode = AppMode::Interactive; // Default to Interactive mode for now
+        // A valid schedule file runs the scheduler; otherwise fall back to the
+        // interactive simulator.
+        let schedule = schedule_path.and_then(|path| match load_schedule(&path, &all_themes) {
+            Ok(schedule) => {
+                eprintln!("Loaded schedule from {:?}", path);
+                for profile in &schedule.profiles {
+                    for diagnostic in &profile.diagnostics {
+                        eprintln!("{}", diagnostic);
+                    }
+                }
+                Some(schedule)
+            }
+            Err(e) => {
+                eprintln!("{:#}", e);
+                None
+            }
+        });
+        // Start from what the appearance file says now, rather than fading to it
+        // once the watcher first reads it.
+        let appearance = schedule
+            .iter()
+            .flat_map(|schedule| &schedule.appearance_sources)
+            .find_map(|source| match source {
+                AppearanceSource::File(path) => read_appearance_file(path),
+                AppearanceSource::Socket(_) => None,
+            })
+            .unwrap_or_default();
+        let app_mode = if schedule.is_some() {
+            AppMode::Scheduler
+        } else {
+            AppMode::Interactive
+        };
 
-        // Set the initial theme to the first one we loaded.
+        // Start from the themes Zed is set to, if they are loaded. Like Zed, a
+        // `"mode": "system"` pair follows the OS appearance.
+        let system_appearance = Appearance::from(cx.window_appearance());
+        let (start_theme_index, end_theme_index, zed_settings_notice) =
+            initial_theme_indices(&all_themes, system_appearance);
         let initial_active_theme = all_themes
-            .first()
+            .get(start_theme_index)
             .map(|theme| theme.interpolatable_theme.clone())
             .expect("Failed to get initial theme");
 
-        let end_theme_index = if all_themes.len() > 1 { 1 } else { 0 };
-
         cx.set_global(AppState {
             app_mode,
             themes: all_themes,
-            start_theme_index: 0, // Default to the first theme
-            end_theme_index,      // Default to the second theme if available
+            start_theme_index,
+            end_theme_index,
+            alpha_mode: AlphaMode::default(),
+            speed,
+            fade_driver,
+            catch_up_arg,
+            schedule: schedule.clone(),
+            appearance,
+            system_appearance,
+            zed_settings,
             active_theme: initial_active_theme,
         });
 
@@ -652,8 +1408,34 @@ fn main() {
                 |window, cx| {
                     let view =
                         cx.new(|cx| AppView::new(cx, sleep_duration_input, fade_duration_input));
-                    view.update(cx, |view, _cx| {
+                    view.update(cx, |view, cx| {
                         window.focus(&view.root_focus_handle);
+                        view.zed_settings_notice = zed_settings_notice;
+                        // A schedule with errors is shown, with its diagnostics, but not run.
+                        view.start_schedule(cx);
+                        simulation::spawn_dashboard_ticker(cx);
+                        cx.observe_window_appearance(window, |_, window, cx| {
+                            let appearance = Appearance::from(window.appearance());
+                            cx.update_global::<AppState, _>(|app_state, _| {
+                                app_state.system_appearance = appearance;
+                            });
+                        })
+                        .detach();
+
+                        let sources = schedule
+                            .map(|schedule| schedule.appearance_sources)
+                            .unwrap_or_default();
+                        if !sources.is_empty() {
+                            let (sender, receiver) = futures::channel::mpsc::unbounded();
+                            for source in sources {
+                                let description = source.to_string();
+                                if let Err(e) = watch_appearance(source, appearance, sender.clone())
+                                {
+                                    eprintln!("Failed to watch appearance {}: {}", description, e);
+                                }
+                            }
+                            simulation::spawn_appearance_listener(cx, receiver);
+                        }
                     });
                     view
                 },
@@ -0,0 +1,159 @@
+use crate::{
+    diff::ThemeDiff,
+    scheduler::FadeStyle,
+    theme::{Color, InterpolatableTheme, Theme},
+};
+
+/// Foreground/background key pairs whose legibility is checked throughout a fade.
+pub const CONTRAST_PAIRS: &[(&str, &str)] = &[
+    ("text", "background"),
+    ("text", "surface.background"),
+    ("editor.foreground", "editor.background"),
+];
+
+/// How many evenly spaced points of the fade are sampled for contrast.
+const FADE_SAMPLES: usize = 20;
+
+/// WCAG AA minimum contrast for body text.
+pub const MIN_READABLE_CONTRAST: f32 = 4.5;
+
+/// How many recommendations the End Theme dropdown shows.
+pub const RECOMMENDATION_COUNT: usize = 3;
+
+#[derive(Clone, Debug)]
+pub struct PairingScore {
+    pub theme_index: usize,
+    pub average_delta_e: f32,
+    /// Fraction (0-1) of changed keys whose hue sweeps further than
+    /// `LARGE_HUE_JUMP_DEGREES` during the fade.
+    pub hue_sweep_risk: f32,
+    /// The lowest contrast ratio seen across `CONTRAST_PAIRS` at any point of the fade.
+    pub min_contrast: f32,
+    /// Lower is better.
+    pub score: f32,
+}
+
+impl PairingScore {
+    pub fn summary(&self) -> String {
+        format!(
+            "ΔE {:.0} · hue risk {:.0}% · min contrast {:.1}",
+            self.average_delta_e,
+            self.hue_sweep_risk * 100.0,
+            self.min_contrast
+        )
+    }
+}
+
+fn relative_luminance(color: Color) -> f32 {
+    fn linearize(c: f32) -> f32 {
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+    0.2126 * linearize(color.rgba.r)
+        + 0.7152 * linearize(color.rgba.g)
+        + 0.0722 * linearize(color.rgba.b)
+}
+
+/// WCAG contrast ratio between two opaque colors, from 1.0 to 21.0.
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    let (lighter, darker) = if la > lb { (la, lb) } else { (lb, la) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// The worst contrast between any `CONTRAST_PAIRS` entry at any sampled point of
+/// a fade from `start` to `end` blended with `style`. Pairs missing from either
+/// theme are skipped.
+pub fn min_contrast_during_fade(
+    start: &InterpolatableTheme,
+    end: &InterpolatableTheme,
+    style: FadeStyle,
+) -> f32 {
+    let mut min_contrast = f32::MAX;
+
+    for (fg_key, bg_key) in CONTRAST_PAIRS {
+        for step in 0..=FADE_SAMPLES {
+            let progress = step as f32 / FADE_SAMPLES as f32;
+            let (Some(foreground), Some(background)) = (
+                style.blend_key(start, end, fg_key, progress),
+                style.blend_key(start, end, bg_key, progress),
+            ) else {
+                break;
+            };
+            let foreground = foreground.composite_over(background);
+            min_contrast = min_contrast.min(contrast_ratio(foreground, background));
+        }
+    }
+
+    if min_contrast == f32::MAX {
+        1.0
+    } else {
+        min_contrast
+    }
+}
+
+pub fn score_pairing(
+    start: &InterpolatableTheme,
+    end: &InterpolatableTheme,
+    theme_index: usize,
+    style: FadeStyle,
+) -> PairingScore {
+    let diff = ThemeDiff::between(start, end);
+    let changed_count = diff.changed().count();
+    let hue_sweep_risk = if changed_count == 0 {
+        0.0
+    } else {
+        diff.changed()
+            .filter(|entry| entry.has_large_hue_jump())
+            .count() as f32
+            / changed_count as f32
+    };
+    let average_delta_e = diff.average_delta_e();
+    let min_contrast = min_contrast_during_fade(start, end, style);
+
+    // ΔE is roughly 0-100. A fade where every key spins round the color wheel
+    // costs as much as a maximally different theme, and every point of contrast
+    // below the readable threshold costs a fifth of that.
+    let score = average_delta_e
+        + hue_sweep_risk * 100.0
+        + (MIN_READABLE_CONTRAST - min_contrast).max(0.0) * 20.0;
+
+    PairingScore {
+        theme_index,
+        average_delta_e,
+        hue_sweep_risk,
+        min_contrast,
+        score,
+    }
+}
+
+/// Ranks every other theme as an end theme for `themes[start_index]`, best first,
+/// for a fade blended with `style`.
+pub fn recommend_end_themes(
+    themes: &[Theme],
+    start_index: usize,
+    style: FadeStyle,
+) -> Vec<PairingScore> {
+    let Some(start) = themes.get(start_index) else {
+        return Vec::new();
+    };
+
+    let mut scores: Vec<PairingScore> = themes
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != start_index)
+        .map(|(index, end)| {
+            score_pairing(
+                &start.interpolatable_theme,
+                &end.interpolatable_theme,
+                index,
+                style,
+            )
+        })
+        .collect();
+    scores.sort_by(|a, b| a.score.total_cmp(&b.score));
+    scores
+}
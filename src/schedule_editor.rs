@@ -0,0 +1,398 @@
+use anyhow::{anyhow, Context as _, Result};
+use gpui::{App, Context, Entity, FocusHandle, SharedString, Subscription};
+use serde_json::{json, Map, Value};
+use std::path::PathBuf;
+
+use crate::{
+    schedule_file::{parse_schedule, write_atomically, LoadedSchedule},
+    scheduler::Easing,
+    text_input::TextInput,
+    theme::Theme,
+    validation::{has_errors, Diagnostic, Severity},
+    AppState, AppView, DropdownState,
+};
+
+// --- SCHEDULE EDITOR ---
+//
+// Edits the `entries` of one profile of the schedule file, or the top-level
+// ones when the file has no profiles. The rest of the file, and the fields of
+// an entry the editor has no control for (such as `color_space` or
+// `timezone`), are written back as they were read.
+
+/// Every editor control shares one tab index, so rows can be reordered
+/// without renumbering their focus handles.
+const EDITOR_TAB_INDEX: isize = 20;
+
+/// One entry being edited.
+pub struct EditorRow {
+    pub theme: String,
+    pub theme_dropdown: DropdownState,
+    pub time_input: Entity<TextInput>,
+    pub fade_input: Entity<TextInput>,
+    pub easing: Easing,
+    pub easing_focus_handle: FocusHandle,
+    pub move_up_focus_handle: FocusHandle,
+    pub move_down_focus_handle: FocusHandle,
+    pub remove_focus_handle: FocusHandle,
+    pub time_message: Option<SharedString>,
+    pub fade_message: Option<SharedString>,
+    pub theme_message: Option<SharedString>,
+    /// The entry as read, so fields without a control survive a save.
+    raw: Map<String, Value>,
+    /// Re-validate the schedule whenever either input is edited.
+    _subscriptions: [Subscription; 2],
+}
+
+impl EditorRow {
+    fn new(raw: Map<String, Value>, cx: &mut Context<AppView>) -> Self {
+        let text = |key: &str| raw.get(key).and_then(Value::as_str).unwrap_or_default();
+        let theme = text("theme").to_string();
+        // An unknown theme keeps its name, and an error, until one is picked.
+        let theme_index = cx
+            .global::<AppState>()
+            .themes
+            .iter()
+            .position(|candidate| candidate.name == theme)
+            .unwrap_or(0);
+        let time_input = new_input("HH:MM or sunset-00:30", text("time"), cx);
+        let fade_input = new_input("No fade, or e.g. 30m", text("fade"), cx);
+        let easing = raw
+            .get("easing")
+            .and_then(|easing| serde_json::from_value(easing.clone()).ok())
+            .unwrap_or_default();
+        let _subscriptions = [
+            cx.observe(&time_input, |view, _, cx| view.validate_schedule_editor(cx)),
+            cx.observe(&fade_input, |view, _, cx| view.validate_schedule_editor(cx)),
+        ];
+
+        Self {
+            theme,
+            theme_dropdown: DropdownState::new(theme_index, EDITOR_TAB_INDEX as usize, cx),
+            time_input,
+            fade_input,
+            easing,
+            easing_focus_handle: editor_focus_handle(cx),
+            move_up_focus_handle: editor_focus_handle(cx),
+            move_down_focus_handle: editor_focus_handle(cx),
+            remove_focus_handle: editor_focus_handle(cx),
+            time_message: None,
+            fade_message: None,
+            theme_message: None,
+            raw,
+            _subscriptions,
+        }
+    }
+
+    /// The entry as it will be saved.
+    fn to_json(&self, cx: &App) -> Value {
+        let mut object = self.raw.clone();
+        let time = self.time_input.read(cx).content.trim().to_string();
+        let fade = self.fade_input.read(cx).content.trim().to_string();
+        object.insert("time".to_string(), Value::from(time));
+        object.insert("theme".to_string(), Value::from(self.theme.clone()));
+        if fade.is_empty() {
+            object.shift_remove("fade");
+        } else {
+            object.insert("fade".to_string(), Value::from(fade));
+        }
+        if self.easing == Easing::default() {
+            object.shift_remove("easing");
+        } else {
+            object.insert("easing".to_string(), json!(self.easing));
+        }
+        Value::Object(object)
+    }
+}
+
+fn editor_focus_handle(cx: &mut Context<AppView>) -> FocusHandle {
+    cx.focus_handle().tab_index(EDITOR_TAB_INDEX).tab_stop(true)
+}
+
+fn new_input(placeholder: &str, content: &str, cx: &mut Context<AppView>) -> Entity<TextInput> {
+    let content = content.to_string();
+    let placeholder = placeholder.to_string();
+    cx.new(|cx| {
+        let mut input = TextInput::new(cx, placeholder);
+        input.focus_handle = cx.focus_handle().tab_index(EDITOR_TAB_INDEX).tab_stop(true);
+        input.content = content.into();
+        input
+    })
+}
+
+pub struct ScheduleEditor {
+    pub path: PathBuf,
+    /// The file as last loaded or saved; the rows are written back into it.
+    document: Value,
+    /// Which of the file's `profiles` is edited, or `None` for the top level.
+    profile: Option<usize>,
+    /// The edited file as last validated.
+    validated: Value,
+    /// Set when the edited profile follows the desktop appearance, so has no
+    /// entries to edit.
+    pub follows_appearance: bool,
+    pub rows: Vec<EditorRow>,
+    /// Problems not tied to a row's field, including any error that stops
+    /// the schedule from being built at all.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Whether any problem, here or on a row, is an error, or the file
+    /// couldn't be loaded. Saving is refused until neither is the case.
+    pub has_errors: bool,
+    /// The outcome of the last load or save.
+    pub status: Option<SharedString>,
+    pub add_focus_handle: FocusHandle,
+    pub save_focus_handle: FocusHandle,
+    pub reload_focus_handle: FocusHandle,
+    pub close_focus_handle: FocusHandle,
+}
+
+impl ScheduleEditor {
+    /// Opens the schedule at `path` on profile `profile_name` (or the file's
+    /// active one). A file that doesn't exist yet starts with no entries; one
+    /// that can't be loaded opens empty, with the error as its status, and
+    /// isn't saved over until a reload succeeds.
+    pub fn open(path: PathBuf, profile_name: Option<&str>, cx: &mut Context<AppView>) -> Self {
+        let mut editor = Self {
+            path,
+            document: Value::Null,
+            profile: None,
+            validated: Value::Null,
+            follows_appearance: false,
+            rows: Vec::new(),
+            diagnostics: Vec::new(),
+            has_errors: false,
+            status: None,
+            add_focus_handle: editor_focus_handle(cx),
+            save_focus_handle: editor_focus_handle(cx),
+            reload_focus_handle: editor_focus_handle(cx),
+            close_focus_handle: editor_focus_handle(cx),
+        };
+        if let Err(e) = editor.load(profile_name, cx) {
+            editor.has_errors = true;
+            editor.status = Some(format!("{:#}", e).into());
+        }
+        editor
+    }
+
+    /// Re-reads the file, discarding unsaved edits.
+    pub fn reload(&mut self, cx: &mut Context<AppView>) -> Result<()> {
+        let profile_name = self
+            .profile
+            .and_then(|index| self.document["profiles"][index]["name"].as_str())
+            .map(str::to_string);
+        self.load(profile_name.as_deref(), cx)?;
+        self.status = Some("Reloaded.".into());
+        Ok(())
+    }
+
+    fn load(&mut self, profile_name: Option<&str>, cx: &mut Context<AppView>) -> Result<()> {
+        let document = if self.path.exists() {
+            let json = std::fs::read_to_string(&self.path)
+                .with_context(|| format!("Failed to read schedule file {:?}", self.path))?;
+            serde_json::from_str(&json)
+                .with_context(|| format!("Failed to parse schedule file {:?}", self.path))?
+        } else {
+            json!({ "entries": [] })
+        };
+        if !document.is_object() {
+            return Err(anyhow!("{:?} doesn't hold a JSON object", self.path));
+        }
+
+        let profiles = document["profiles"].as_array();
+        let profile = profiles
+            .filter(|profiles| !profiles.is_empty())
+            .map(|profiles| {
+                let name = profile_name.or(document["active_profile"].as_str());
+                profiles
+                    .iter()
+                    .position(|profile| profile["name"].as_str() == name)
+                    .unwrap_or(0)
+            });
+        let body = match profile {
+            Some(index) => &document["profiles"][index],
+            None => &document,
+        };
+
+        self.follows_appearance = body.get("appearance").is_some();
+        self.rows = body["entries"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.as_object().cloned())
+            .map(|raw| EditorRow::new(raw, cx))
+            .collect();
+        self.document = document;
+        self.profile = profile;
+        self.status = None;
+        self.validate(&cx.global::<AppState>().themes, cx);
+        Ok(())
+    }
+
+    pub fn add_row(&mut self, cx: &mut Context<AppView>) {
+        let theme = cx
+            .global::<AppState>()
+            .themes
+            .first()
+            .map_or(String::new(), |theme| theme.name.clone());
+        let mut raw = Map::new();
+        raw.insert("time".to_string(), Value::from("12:00"));
+        raw.insert("theme".to_string(), Value::from(theme));
+        self.rows.push(EditorRow::new(raw, cx));
+        self.validate(&cx.global::<AppState>().themes, cx);
+    }
+
+    pub fn remove_row(&mut self, index: usize, themes: &[Theme], cx: &App) {
+        if index < self.rows.len() {
+            self.rows.remove(index);
+            self.validate(themes, cx);
+        }
+    }
+
+    /// Swaps row `index` with the one above (`up`) or below it.
+    pub fn move_row(&mut self, index: usize, up: bool, themes: &[Theme], cx: &App) {
+        let other = if up {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1)
+        };
+        if let Some(other) = other.filter(|other| *other < self.rows.len()) {
+            self.rows.swap(index, other);
+            self.validate(themes, cx);
+        }
+    }
+
+    pub fn set_theme(&mut self, index: usize, theme_index: usize, themes: &[Theme], cx: &App) {
+        if let Some(row) = self.rows.get_mut(index) {
+            row.theme = themes[theme_index].name.clone();
+            row.theme_dropdown.preview_index = theme_index;
+            row.theme_dropdown.is_open = false;
+            self.validate(themes, cx);
+        }
+    }
+
+    pub fn cycle_easing(&mut self, index: usize) {
+        if let Some(row) = self.rows.get_mut(index) {
+            row.easing = row.easing.next();
+        }
+    }
+
+    /// The file as it would be saved now.
+    fn edited_document(&self, cx: &App) -> Value {
+        let mut document = self.document.clone();
+        if self.follows_appearance {
+            return document;
+        }
+        let body = match self.profile {
+            Some(index) => &mut document["profiles"][index],
+            None => &mut document,
+        };
+        if let Some(body) = body.as_object_mut() {
+            let entries = self.rows.iter().map(|row| row.to_json(cx)).collect();
+            body.insert("entries".to_string(), Value::Array(entries));
+        }
+        document
+    }
+
+    /// Validates again if the inputs have changed since last time. Text inputs
+    /// also notify for cursor blinks, so most calls find nothing new.
+    pub fn revalidate(&mut self, themes: &[Theme], cx: &App) -> bool {
+        let changed = self.edited_document(cx) != self.validated;
+        if changed {
+            self.validate(themes, cx);
+        }
+        changed
+    }
+
+    /// Checks the edited schedule as `load_schedule` would, showing each
+    /// problem beside the field it is about where there is one.
+    pub fn validate(&mut self, themes: &[Theme], cx: &App) {
+        self.diagnostics.clear();
+        for row in &mut self.rows {
+            row.time_message = None;
+            row.fade_message = None;
+            row.theme_message = None;
+        }
+
+        // Until the file loads there is nothing to check, and nothing to save.
+        if self.document.is_null() {
+            self.has_errors = true;
+            return;
+        }
+        self.validated = self.edited_document(cx);
+        let diagnostics = match parse_schedule(&self.validated, themes) {
+            Ok(loaded) => loaded.profiles[self.profile.unwrap_or(0)]
+                .diagnostics
+                .clone(),
+            Err(e) => vec![error_diagnostic(&e)],
+        };
+        self.has_errors = has_errors(&diagnostics);
+        for diagnostic in diagnostics {
+            if !self.attach_to_row(&diagnostic) {
+                self.diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    /// Shows `diagnostic` under the row field it names; false if it names none.
+    fn attach_to_row(&mut self, diagnostic: &Diagnostic) -> bool {
+        let prefix = match self.profile {
+            Some(index) => format!("profiles[{}].", index),
+            None => String::new(),
+        };
+        let Some((label, field)) = diagnostic.field.rsplit_once('.') else {
+            return false;
+        };
+        let Some(index) = label
+            .strip_prefix(&prefix)
+            .and_then(|label| label.strip_prefix("entries["))
+            .and_then(|label| label.strip_suffix(']'))
+            .and_then(|index| index.parse::<usize>().ok())
+        else {
+            return false;
+        };
+        let Some(row) = self.rows.get_mut(index) else {
+            return false;
+        };
+        let slot = match field {
+            "time" => &mut row.time_message,
+            "fade" => &mut row.fade_message,
+            "theme" => &mut row.theme_message,
+            _ => return false,
+        };
+        if slot.is_none() {
+            let message = match diagnostic.severity {
+                Severity::Error => diagnostic.message.clone(),
+                Severity::Warning => format!("Warning: {}", diagnostic.message),
+            };
+            *slot = Some(message.into());
+        }
+        true
+    }
+
+    /// Writes the edits to the file and returns the schedule it now holds.
+    /// Nothing is written while any error remains.
+    pub fn save(&mut self, themes: &[Theme], cx: &App) -> Result<LoadedSchedule> {
+        self.validate(themes, cx);
+        if self.has_errors {
+            return Err(anyhow!("Fix the errors shown before saving."));
+        }
+
+        let document = self.edited_document(cx);
+        let mut loaded = parse_schedule(&document, themes)?;
+        let json = serde_json::to_string_pretty(&document)? + "\n";
+        write_atomically(&self.path, &json)
+            .with_context(|| format!("Failed to write schedule file {:?}", self.path))?;
+        loaded.path = self.path.clone();
+        self.document = document;
+        Ok(loaded)
+    }
+}
+
+/// Turns an error from building the schedule, such as
+/// `entries[2].time: "25:00" is not a time`, into a diagnostic for its field.
+fn error_diagnostic(error: &anyhow::Error) -> Diagnostic {
+    let text = format!("{:#}", error);
+    match text.split_once(": ") {
+        Some((field, message)) if !field.contains(' ') => Diagnostic::error(field, message),
+        _ => Diagnostic::error("schedule", text),
+    }
+}
@@ -0,0 +1,847 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{Duration, NaiveDate, Utc, Weekday};
+use serde::Deserialize;
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use crate::{
+    appearance::{Appearance, AppearanceConfig, AppearanceSource},
+    hooks::{Hook, DEFAULT_HOOK_TIMEOUT},
+    scheduler::{
+        CatchUp, DateRange, DayPlan, DaySet, DayVariant, Easing, FadeStyle, ScheduleDays,
+        ScheduleEntry, ScheduleTime, ScheduleZone, TransitionKind,
+    },
+    solar::GeoLocation,
+    theme::{AlphaMode, ColorSpace, Theme},
+    validation::{validate_schedule, Diagnostic},
+};
+
+// --- SCHEDULE FILE ---
+//
+// A schedule is described in JSON, with themes referenced by name:
+//
+//     {
+//       "location": { "latitude": 51.5, "longitude": -0.13 },
+//       "timezone": "Europe/London",
+//       "entries": [
+//         { "time": "sunrise", "theme": "One Light", "fade": "30m", "easing": "ease_in_out" },
+//         { "time": "sunset-00:30", "theme": "One Dark", "fade": "1h", "color_space": "lab" }
+//       ],
+//       "variants": [
+//         { "days": ["weekends"], "entries": [{ "time": "10:00", "theme": "Ayu Light" }] },
+//         { "dates": ["2026-12-24..2026-12-26"], "entries": [{ "time": "09:00", "theme": "Gruvbox" }] }
+//       ]
+//     }
+//
+// On the days a variant matches, its entries replace the top-level ones. Each
+// day, variants with `dates` are tried first, then those with only `days`, and
+// within each group the first listed that matches wins.
+//
+// Instead of top-level `entries` and `variants`, a file may list named
+// profiles to switch between at runtime. Each takes the same fields as the
+// top level, and inherits the file's `location` and `timezone` unless it sets
+// its own:
+//
+//     {
+//       "location": { "latitude": 51.5, "longitude": -0.13 },
+//       "active_profile": "office",
+//       "profiles": [
+//         { "name": "office", "entries": [...] },
+//         { "name": "travel", "timezone": "local", "entries": [...] }
+//       ]
+//     }
+//
+// A profile (or the top level) can instead follow the desktop's light/dark
+// appearance, reported through a watched file or a local socket:
+//
+//     {
+//       "appearance_signal": { "file": "~/.cache/zed-theme-fader/appearance" },
+//       "appearance": { "light": "One Light", "dark": "One Dark", "fade": "3s" }
+//     }
+//
+// Commands to run as the theme changes are listed under `hooks`; see hooks.rs.
+// How the schedule catches up after the clock jumps, e.g. on waking from
+// suspend, is set by `"catch_up": "3s"`; "0s" jumps straight there. The
+// `--catch-up` flag overrides it.
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScheduleFile {
+    location: Option<LocationSpec>,
+    timezone: Option<String>,
+    #[serde(default)]
+    entries: Vec<EntrySpec>,
+    #[serde(default)]
+    variants: Vec<VariantSpec>,
+    #[serde(default)]
+    profiles: Vec<ProfileSpec>,
+    /// The profile run at startup. Defaults to the first.
+    active_profile: Option<String>,
+    appearance: Option<AppearanceSpec>,
+    appearance_signal: Option<SignalSpec>,
+    #[serde(default)]
+    hooks: Vec<HookSpec>,
+    /// The fade after a clock jump, e.g. "3s"; "0s" jumps straight there.
+    catch_up: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfileSpec {
+    name: String,
+    location: Option<LocationSpec>,
+    timezone: Option<String>,
+    #[serde(default)]
+    entries: Vec<EntrySpec>,
+    #[serde(default)]
+    variants: Vec<VariantSpec>,
+    appearance: Option<AppearanceSpec>,
+}
+
+/// Fades between two themes as the desktop appearance changes, instead of by time.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AppearanceSpec {
+    light: String,
+    dark: String,
+    fade: Option<String>,
+    #[serde(default)]
+    easing: Easing,
+    #[serde(default)]
+    color_space: ColorSpace,
+    #[serde(default)]
+    alpha_mode: AlphaMode,
+}
+
+/// Where appearance changes come from. "~/" is expanded in both paths.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SignalSpec {
+    /// A file holding "light" or "dark".
+    file: Option<String>,
+    /// A Unix socket path to listen on for "light" or "dark".
+    socket: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HookSpec {
+    on: TransitionKind,
+    /// The program and its arguments, e.g. `["notify-send", "Theme changed"]`.
+    command: Vec<String>,
+    /// e.g. "5s"; defaults to `DEFAULT_HOOK_TIMEOUT`.
+    timeout: Option<String>,
+}
+
+/// The parts of the file, top-level or in a profile, that describe one schedule.
+struct ProfileBody<'a> {
+    entries: &'a [EntrySpec],
+    variants: &'a [VariantSpec],
+    appearance: Option<&'a AppearanceSpec>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LocationSpec {
+    latitude: f64,
+    longitude: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VariantSpec {
+    /// Weekday names ("mon", "tuesday"), "weekdays" or "weekends". Defaults to every day.
+    days: Option<Vec<String>>,
+    /// Single dates ("2026-12-25") or inclusive ranges ("2026-12-24..2026-12-26").
+    dates: Option<Vec<String>>,
+    entries: Vec<EntrySpec>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EntrySpec {
+    time: String,
+    theme: String,
+    /// e.g. "45s", "30m", "1h30m"; a bare number is seconds. Defaults to no fade.
+    fade: Option<String>,
+    #[serde(default)]
+    easing: Easing,
+    #[serde(default)]
+    color_space: ColorSpace,
+    #[serde(default)]
+    alpha_mode: AlphaMode,
+    /// Overrides the file's `timezone` for this entry.
+    timezone: Option<String>,
+}
+
+/// The profiles read from a schedule file.
+#[derive(Clone)]
+pub struct LoadedSchedule {
+    pub path: PathBuf,
+    /// Never empty. A file without `profiles` has a single one named "default".
+    pub profiles: Vec<ScheduleProfile>,
+    /// Index into `profiles` of the one being run.
+    pub active_profile: usize,
+    /// What profiles with an `appearance` listen to.
+    pub appearance_sources: Vec<AppearanceSource>,
+    /// Commands run on transitions, whichever profile is active.
+    pub hooks: Vec<Hook>,
+    /// How to catch up after a clock jump, if the file says.
+    pub catch_up: Option<CatchUp>,
+}
+
+impl LoadedSchedule {
+    pub fn active(&self) -> &ScheduleProfile {
+        &self.profiles[self.active_profile]
+    }
+
+    pub fn profile_index(&self, name: &str) -> Option<usize> {
+        self.profiles
+            .iter()
+            .position(|profile| profile.name == name)
+    }
+}
+
+/// One named schedule, ready to hand to `ThemeScheduler`.
+#[derive(Clone)]
+pub struct ScheduleProfile {
+    pub name: String,
+    /// Empty when the profile follows the appearance; see `schedule_for`.
+    pub entries: Arc<Vec<ScheduleEntry>>,
+    pub appearance: Option<AppearanceConfig>,
+    /// The theme each entry fades to, by entry index.
+    pub theme_names: Vec<String>,
+    /// Where each entry came from in the file, e.g. `variants[0].entries[1]`.
+    pub labels: Vec<String>,
+    /// Problems found after parsing. The scheduler isn't started if any is an error.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ScheduleProfile {
+    /// The schedule to run while the desktop has `appearance`.
+    pub fn schedule_for(&self, appearance: Appearance) -> Arc<Vec<ScheduleEntry>> {
+        match &self.appearance {
+            Some(config) => config.schedule(appearance),
+            None => self.entries.clone(),
+        }
+    }
+
+    /// The name of the theme entry `index` of `schedule_for(appearance)` shows.
+    pub fn theme_name(&self, index: usize, appearance: Appearance) -> &str {
+        match &self.appearance {
+            Some(config) => config.theme_name(appearance),
+            None => &self.theme_names[index],
+        }
+    }
+}
+
+/// Where the schedule is read from when `--schedule` isn't given.
+pub fn default_schedule_path() -> Option<PathBuf> {
+    let home = std::env::var("HOME").ok()?;
+    Some(PathBuf::from(home).join(".config/zed-theme-fader/schedule.json"))
+}
+
+/// Reads and validates the schedule at `path`, resolving theme names against
+/// `themes`. Malformed files are errors naming the offending field, e.g.
+/// `entries[2].time`; schedules that parse but won't behave as written (such
+/// as unknown themes or overlapping fades) are reported as diagnostics of the
+/// profile they are in.
+pub fn load_schedule(path: &Path, themes: &[Theme]) -> Result<LoadedSchedule> {
+    let json = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read schedule file {:?}", path))?;
+    let file: ScheduleFile = serde_json::from_str(&json)
+        .with_context(|| format!("Failed to parse schedule file {:?}", path))?;
+    let mut loaded = check_schedule(&file, themes)
+        .with_context(|| format!("Invalid schedule file {:?}", path))?;
+    loaded.path = path.to_path_buf();
+    Ok(loaded)
+}
+
+/// Builds and validates a schedule that hasn't been saved yet, such as the
+/// one in the schedule editor. Errors are the same as `load_schedule`'s,
+/// without the file name.
+pub fn parse_schedule(json: &serde_json::Value, themes: &[Theme]) -> Result<LoadedSchedule> {
+    let file = ScheduleFile::deserialize(json)?;
+    check_schedule(&file, themes)
+}
+
+fn check_schedule(file: &ScheduleFile, themes: &[Theme]) -> Result<LoadedSchedule> {
+    let mut loaded = build_profiles(file, themes)?;
+    for profile in &mut loaded.profiles {
+        profile.diagnostics.extend(validate_schedule(
+            &profile.entries,
+            &profile.labels,
+            Utc::now(),
+        ));
+        profile
+            .diagnostics
+            .sort_by_key(|diagnostic| std::cmp::Reverse(diagnostic.severity));
+    }
+    Ok(loaded)
+}
+
+fn build_profiles(file: &ScheduleFile, themes: &[Theme]) -> Result<LoadedSchedule> {
+    let location = file.location.as_ref().map(parse_location);
+    let default_zone = match &file.timezone {
+        Some(name) => parse_zone(name).map_err(|message| anyhow!("timezone: {}", message))?,
+        None => ScheduleZone::SystemLocal,
+    };
+
+    let appearance_sources = file
+        .appearance_signal
+        .iter()
+        .flat_map(|signal| {
+            let file = signal
+                .file
+                .as_deref()
+                .map(expand_home)
+                .map(AppearanceSource::File);
+            let socket = signal
+                .socket
+                .as_deref()
+                .map(expand_home)
+                .map(AppearanceSource::Socket);
+            file.into_iter().chain(socket)
+        })
+        .collect::<Vec<_>>();
+
+    let hooks = file
+        .hooks
+        .iter()
+        .enumerate()
+        .map(|(index, spec)| parse_hook(index, spec))
+        .collect::<Result<Vec<_>>>()?;
+    let catch_up = file
+        .catch_up
+        .as_deref()
+        .map(parse_catch_up)
+        .transpose()
+        .map_err(|message| anyhow!("catch_up: {}", message))?;
+
+    let mut loaded = if file.profiles.is_empty() {
+        if let Some(name) = &file.active_profile {
+            return Err(anyhow!(
+                "active_profile: \"{}\" is set, but the file has no \"profiles\"",
+                name
+            ));
+        }
+        let profile = build_profile(
+            "default",
+            "",
+            location,
+            default_zone,
+            ProfileBody {
+                entries: &file.entries,
+                variants: &file.variants,
+                appearance: file.appearance.as_ref(),
+            },
+            themes,
+        )?;
+        LoadedSchedule {
+            path: PathBuf::new(),
+            profiles: vec![profile],
+            active_profile: 0,
+            appearance_sources,
+            hooks: Vec::new(),
+            catch_up: None,
+        }
+    } else {
+        if !file.entries.is_empty() || !file.variants.is_empty() || file.appearance.is_some() {
+            return Err(anyhow!(
+                "profiles: give either top-level \"entries\", \"appearance\" or \"profiles\""
+            ));
+        }
+        build_named_profiles(file, location, default_zone, appearance_sources, themes)?
+    };
+
+    loaded.hooks = hooks;
+    loaded.catch_up = catch_up;
+    let listens = !loaded.appearance_sources.is_empty();
+    for profile in &mut loaded.profiles {
+        if profile.appearance.is_some() && !listens {
+            profile.diagnostics.push(Diagnostic::warning(
+                "appearance_signal",
+                "no file or socket is given, so the light theme is always shown",
+            ));
+        }
+    }
+    Ok(loaded)
+}
+
+fn build_named_profiles(
+    file: &ScheduleFile,
+    location: Option<GeoLocation>,
+    default_zone: ScheduleZone,
+    appearance_sources: Vec<AppearanceSource>,
+    themes: &[Theme],
+) -> Result<LoadedSchedule> {
+    let mut profiles: Vec<ScheduleProfile> = Vec::new();
+    for (index, spec) in file.profiles.iter().enumerate() {
+        let prefix = format!("profiles[{}].", index);
+        if let Some(other) = profiles
+            .iter()
+            .position(|profile| profile.name == spec.name)
+        {
+            return Err(anyhow!(
+                "{}name: \"{}\" is already the name of profiles[{}]",
+                prefix,
+                spec.name,
+                other
+            ));
+        }
+        let zone = match &spec.timezone {
+            Some(name) => {
+                parse_zone(name).map_err(|message| anyhow!("{}timezone: {}", prefix, message))?
+            }
+            None => default_zone,
+        };
+        profiles.push(build_profile(
+            &spec.name,
+            &prefix,
+            spec.location.as_ref().map(parse_location).or(location),
+            zone,
+            ProfileBody {
+                entries: &spec.entries,
+                variants: &spec.variants,
+                appearance: spec.appearance.as_ref(),
+            },
+            themes,
+        )?);
+    }
+
+    let active_profile = match &file.active_profile {
+        Some(name) => profiles
+            .iter()
+            .position(|profile| &profile.name == name)
+            .ok_or_else(|| anyhow!("active_profile: no profile is named \"{}\"", name))?,
+        None => 0,
+    };
+    Ok(LoadedSchedule {
+        path: PathBuf::new(),
+        profiles,
+        active_profile,
+        appearance_sources,
+        hooks: Vec::new(),
+        catch_up: None,
+    })
+}
+
+/// Builds one profile's entries. `prefix` (e.g. `profiles[1].`) starts the
+/// field path of everything reported about it.
+fn build_profile(
+    name: &str,
+    prefix: &str,
+    location: Option<GeoLocation>,
+    default_zone: ScheduleZone,
+    body: ProfileBody<'_>,
+    themes: &[Theme],
+) -> Result<ScheduleProfile> {
+    let ProfileBody {
+        entries: entry_specs,
+        variants: variant_specs,
+        appearance,
+    } = body;
+    if let Some(appearance) = appearance {
+        if !entry_specs.is_empty() || !variant_specs.is_empty() {
+            return Err(anyhow!(
+                "{}appearance: give either \"entries\" or \"appearance\", not both",
+                prefix
+            ));
+        }
+        return build_appearance_profile(name, prefix, appearance, themes);
+    }
+
+    let mut plan = DayPlan::default();
+    for (index, variant) in variant_specs.iter().enumerate() {
+        let field = |name: &str| format!("{}variants[{}].{}", prefix, index, name);
+        if variant.days.is_none() && variant.dates.is_none() {
+            return Err(anyhow!(
+                "{}variants[{}]: give \"days\", \"dates\" or both",
+                prefix,
+                index
+            ));
+        }
+        let days = match &variant.days {
+            Some(names) => {
+                parse_days(names).map_err(|message| anyhow!("{}: {}", field("days"), message))?
+            }
+            None => DaySet::ALL,
+        };
+        let dates = variant
+            .dates
+            .iter()
+            .flatten()
+            .map(|text| parse_date_range(text))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|message| anyhow!("{}: {}", field("dates"), message))?;
+        plan.variants.push(DayVariant { days, dates });
+    }
+    let plan = Arc::new(plan);
+
+    let groups = std::iter::once((format!("{}entries", prefix), entry_specs, None)).chain(
+        variant_specs.iter().enumerate().map(|(index, variant)| {
+            (
+                format!("{}variants[{}].entries", prefix, index),
+                variant.entries.as_slice(),
+                Some(index),
+            )
+        }),
+    );
+
+    let mut entries = Vec::new();
+    let mut theme_names = Vec::new();
+    let mut labels = Vec::new();
+    let mut diagnostics = Vec::new();
+    for (group, specs, variant) in groups {
+        for (index, spec) in specs.iter().enumerate() {
+            let label = format!("{}[{}]", group, index);
+            let field = |name: &str| format!("{}.{}", label, name);
+
+            let time = ScheduleTime::parse(&spec.time, location)
+                .map_err(|message| anyhow!("{}: {}", field("time"), message))?;
+            let Some(theme) = themes.iter().find(|theme| theme.name == spec.theme) else {
+                diagnostics.push(Diagnostic::error(
+                    field("theme"),
+                    format!("unknown theme \"{}\"", spec.theme),
+                ));
+                continue;
+            };
+            let fade_duration = match &spec.fade {
+                Some(text) => parse_duration(text)
+                    .map_err(|message| anyhow!("{}: {}", field("fade"), message))?,
+                None => Duration::zero(),
+            };
+            let zone = match &spec.timezone {
+                Some(name) => parse_zone(name)
+                    .map_err(|message| anyhow!("{}: {}", field("timezone"), message))?,
+                None => default_zone,
+            };
+
+            entries.push(ScheduleEntry {
+                time,
+                zone,
+                theme: theme.interpolatable_theme.clone(),
+                label: theme.label(),
+                fade_duration,
+                style: FadeStyle {
+                    easing: spec.easing,
+                    alpha_mode: spec.alpha_mode,
+                    color_space: spec.color_space,
+                },
+                days: ScheduleDays {
+                    plan: plan.clone(),
+                    variant,
+                },
+            });
+            theme_names.push(spec.theme.clone());
+            labels.push(label);
+        }
+    }
+
+    if entries.is_empty() && diagnostics.is_empty() {
+        return Err(anyhow!(
+            "{}entries: the schedule needs at least one entry",
+            prefix
+        ));
+    }
+    Ok(ScheduleProfile {
+        name: name.to_string(),
+        entries: Arc::new(entries),
+        appearance: None,
+        theme_names,
+        labels,
+        diagnostics,
+    })
+}
+
+fn build_appearance_profile(
+    name: &str,
+    prefix: &str,
+    spec: &AppearanceSpec,
+    themes: &[Theme],
+) -> Result<ScheduleProfile> {
+    let field = |name: &str| format!("{}appearance.{}", prefix, name);
+    let mut diagnostics = Vec::new();
+    let mut find_theme = |name: &str, key: &str| {
+        let theme = themes.iter().find(|theme| theme.name == name);
+        if theme.is_none() {
+            diagnostics.push(Diagnostic::error(
+                field(key),
+                format!("unknown theme \"{}\"", name),
+            ));
+        }
+        theme
+    };
+    let light = find_theme(&spec.light, "light");
+    let dark = find_theme(&spec.dark, "dark");
+    let fade_duration = match &spec.fade {
+        Some(text) => {
+            parse_duration(text).map_err(|message| anyhow!("{}: {}", field("fade"), message))?
+        }
+        None => Duration::zero(),
+    };
+
+    let appearance = match (light, dark) {
+        (Some(light), Some(dark)) => Some(AppearanceConfig {
+            light: light.interpolatable_theme.clone(),
+            dark: dark.interpolatable_theme.clone(),
+            light_label: light.label(),
+            dark_label: dark.label(),
+            fade_duration,
+            style: FadeStyle {
+                easing: spec.easing,
+                alpha_mode: spec.alpha_mode,
+                color_space: spec.color_space,
+            },
+        }),
+        _ => None,
+    };
+    Ok(ScheduleProfile {
+        name: name.to_string(),
+        entries: Arc::new(Vec::new()),
+        appearance,
+        theme_names: Vec::new(),
+        labels: Vec::new(),
+        diagnostics,
+    })
+}
+
+fn parse_hook(index: usize, spec: &HookSpec) -> Result<Hook> {
+    let field = |name: &str| format!("hooks[{}].{}", index, name);
+    if spec.command.is_empty() {
+        return Err(anyhow!(
+            "{}: give the program to run and its arguments",
+            field("command")
+        ));
+    }
+    let timeout = match &spec.timeout {
+        Some(text) => parse_duration(text)
+            .and_then(|timeout| {
+                timeout
+                    .to_std()
+                    .ok()
+                    .filter(|timeout| !timeout.is_zero())
+                    .ok_or_else(|| "the timeout must be longer than zero".to_string())
+            })
+            .map_err(|message| anyhow!("{}: {}", field("timeout"), message))?,
+        None => DEFAULT_HOOK_TIMEOUT,
+    };
+    Ok(Hook {
+        on: spec.on,
+        command: spec.command.clone(),
+        timeout,
+    })
+}
+
+/// Parses a catch-up fade length, where zero means jumping straight there.
+fn parse_catch_up(text: &str) -> Result<CatchUp, String> {
+    let duration = parse_duration(text)?;
+    Ok(if duration.is_zero() {
+        CatchUp::Jump
+    } else {
+        CatchUp::Fade(duration)
+    })
+}
+
+/// Writes `contents` to `path` by way of a temporary file in the same
+/// directory, so nothing watching `path` ever reads a half-written file. The
+/// new file keeps the old one's permissions, and reaches the disk before it
+/// takes the old one's place.
+pub fn write_atomically(path: &Path, contents: &str) -> std::io::Result<()> {
+    // Numbers this process's writes; with the process id, it keeps two writers
+    // from sharing a temporary file.
+    static WRITES: AtomicU64 = AtomicU64::new(0);
+
+    let directory = path.parent().unwrap_or(Path::new("."));
+    std::fs::create_dir_all(directory)?;
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(format!(
+        ".{}-{}.tmp",
+        std::process::id(),
+        WRITES.fetch_add(1, Ordering::Relaxed)
+    ));
+    let temporary = PathBuf::from(temporary);
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&temporary)?;
+    let result = (|| {
+        file.write_all(contents.as_bytes())?;
+        match std::fs::metadata(path) {
+            Ok(metadata) => file.set_permissions(metadata.permissions())?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        file.sync_all()?;
+        std::fs::rename(&temporary, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temporary);
+    }
+    result
+}
+
+/// Expands a leading "~/" to the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn parse_location(location: &LocationSpec) -> GeoLocation {
+    GeoLocation {
+        latitude: location.latitude,
+        longitude: location.longitude,
+    }
+}
+
+fn parse_days(names: &[String]) -> Result<DaySet, String> {
+    if names.is_empty() {
+        return Err("list at least one day".to_string());
+    }
+    let mut days = Vec::new();
+    for name in names {
+        match name.to_ascii_lowercase().as_str() {
+            "weekdays" => days.extend([
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ]),
+            "weekends" => days.extend([Weekday::Sat, Weekday::Sun]),
+            other => days.push(other.parse::<Weekday>().map_err(|_| {
+                format!(
+                    "\"{}\" is not a weekday, \"weekdays\" or \"weekends\"",
+                    name
+                )
+            })?),
+        }
+    }
+    Ok(days.into_iter().collect())
+}
+
+/// Parses "2026-12-25" or "2026-12-24..2026-12-26".
+fn parse_date_range(text: &str) -> Result<DateRange, String> {
+    let parse = |date: &str| {
+        NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+            .map_err(|_| format!("\"{}\" is not a YYYY-MM-DD date", date.trim()))
+    };
+    let range = match text.split_once("..") {
+        Some((start, end)) => DateRange {
+            start: parse(start)?,
+            end: parse(end)?,
+        },
+        None => {
+            let date = parse(text)?;
+            DateRange {
+                start: date,
+                end: date,
+            }
+        }
+    };
+    if range.start > range.end {
+        return Err(format!("\"{}\" ends before it starts", text));
+    }
+    Ok(range)
+}
+
+fn parse_zone(name: &str) -> Result<ScheduleZone, String> {
+    ScheduleZone::parse(name).ok_or_else(|| {
+        format!(
+            "unknown timezone \"{}\"; use an IANA name such as \"Europe/London\", or \"local\"",
+            name
+        )
+    })
+}
+
+/// Parses durations such as "90", "45s", "30m" or "1h30m".
+fn parse_duration(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    if let Ok(seconds) = text.parse::<u32>() {
+        return Ok(Duration::seconds(seconds as i64));
+    }
+
+    let invalid = || {
+        format!(
+            "\"{}\" is not a duration like \"45s\", \"30m\" or \"1h30m\"",
+            text
+        )
+    };
+    let too_long = || format!("\"{}\" is too long a duration", text);
+    let mut total = Duration::zero();
+    let mut digits = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let amount: i64 = digits.parse().map_err(|_| invalid())?;
+        let part = match c {
+            'h' => Duration::try_hours(amount),
+            'm' => Duration::try_minutes(amount),
+            's' => Duration::try_seconds(amount),
+            _ => return Err(invalid()),
+        };
+        total = part
+            .and_then(|part| total.checked_add(&part))
+            .ok_or_else(too_long)?;
+        digits.clear();
+    }
+    if !digits.is_empty() || text.is_empty() {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("90"), Ok(Duration::seconds(90)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::minutes(90)));
+        assert_eq!(parse_duration(" 45s "), Ok(Duration::seconds(45)));
+        assert!(parse_duration("30x").is_err());
+        assert!(parse_duration("").is_err());
+    }
+
+    #[test]
+    fn rejects_durations_too_long_to_represent() {
+        assert_eq!(
+            parse_duration("99999999999999h"),
+            Err("\"99999999999999h\" is too long a duration".to_string())
+        );
+        assert!(parse_duration("2562047788015h2562047788015h").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_atomically_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let directory =
+            std::env::temp_dir().join(format!("theme-fader-write-{}", std::process::id()));
+        let path = directory.join("settings.json");
+        write_atomically(&path, "{}").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+
+        write_atomically(&path, "{ \"theme\": \"One Dark\" }").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let leftovers = std::fs::read_dir(&directory).unwrap().count();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(contents, "{ \"theme\": \"One Dark\" }");
+        assert_eq!(leftovers, 1);
+    }
+}
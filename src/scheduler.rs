@@ -1,97 +1,1030 @@
-use chrono::{Duration, Local, NaiveTime};
-use futures::channel::mpsc;
-use std::{sync::Arc, thread, time::Duration as StdDuration};
-use tracing::info;
+use chrono::{
+    DateTime, Datelike, Duration, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use futures::{channel::mpsc, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc as std_mpsc, Arc, Mutex,
+    },
+    thread,
+    time::Duration as StdDuration,
+};
+use tracing::{error, info};
 
 use crate::{
+    clock::Clock,
+    solar::{solar_event_time, GeoLocation, SolarEvent},
     state::SimulationState,
-    theme::{lerp_theme, InterpolatableTheme},
+    theme::{
+        lerp_key_in, lerp_theme_in, AlphaMode, Color, ColorSpace, InterpolatableTheme, Theme,
+        ThemeLabel,
+    },
     AppMode,
 };
 
 pub enum SchedulerEvent {
     ThemeUpdate(InterpolatableTheme),
     StateChange(SimulationState),
+    /// With `FadeDriver::Frames`, a fade the UI should animate itself. Sent
+    /// again with shifted instants after a pause.
+    FadeStarted(FadeSpan),
+    /// A fade has begun or ended, or a theme has landed on screen. Sent
+    /// alongside the frames and states above, for sinks such as hooks that
+    /// follow transitions rather than frames.
+    Transition(Transition),
     Finished,
 }
 
+/// Which moment a `Transition` marks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionKind {
+    FadeStarted,
+    FadeFinished,
+    /// A theme is on screen unblended: at the end of a fade, or shown at once.
+    ThemeApplied,
+}
+
+impl TransitionKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::FadeStarted => "fade_started",
+            Self::FadeFinished => "fade_finished",
+            Self::ThemeApplied => "theme_applied",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Transition {
+    pub kind: TransitionKind,
+    /// The theme being faded to, or now shown.
+    pub theme: ThemeLabel,
+    /// How far through the fade; a fade joined late starts part-way.
+    pub progress: f32,
+}
+
+/// What happens when the scheduler finds the clock has jumped (after suspend,
+/// or when resuming from a pause in a real schedule).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CatchUp {
+    /// Show the schedule's current theme at once, continuing any fade from
+    /// the right point.
+    Jump,
+    /// Fade from what was on screen to the schedule's current theme.
+    Fade(Duration),
+}
+
+impl Default for CatchUp {
+    fn default() -> Self {
+        Self::Fade(Duration::seconds(3))
+    }
+}
+
+/// Who computes the intermediate themes of a fade.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FadeDriver {
+    /// The scheduler thread sends a theme every `FRAME_INTERVAL`.
+    #[default]
+    Thread,
+    /// The scheduler sends one `FadeStarted`, and the UI interpolates on each
+    /// animation frame, so the fade is vsync-aligned and idles while hidden.
+    Frames,
+}
+
+impl FadeDriver {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "thread" => Some(Self::Thread),
+            "frames" => Some(Self::Frames),
+            _ => None,
+        }
+    }
+}
+
+/// How a fade's progress over time maps to how far the colors have blended.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Self::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Linear => "Linear",
+            Self::EaseIn => "Ease In",
+            Self::EaseOut => "Ease Out",
+            Self::EaseInOut => "Ease In-Out",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Linear => Self::EaseIn,
+            Self::EaseIn => Self::EaseOut,
+            Self::EaseOut => Self::EaseInOut,
+            Self::EaseInOut => Self::Linear,
+        }
+    }
+}
+
+/// How an entry's theme is blended in from the one before it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FadeStyle {
+    pub easing: Easing,
+    pub alpha_mode: AlphaMode,
+    pub color_space: ColorSpace,
+}
+
+impl FadeStyle {
+    /// The theme `progress` (0-1, linear in time) of the way from `from` to `to`.
+    pub fn blend(
+        &self,
+        from: &InterpolatableTheme,
+        to: &InterpolatableTheme,
+        progress: f32,
+    ) -> InterpolatableTheme {
+        lerp_theme_in(
+            from,
+            to,
+            self.easing.apply(progress),
+            self.alpha_mode,
+            self.color_space,
+        )
+    }
+
+    /// A single key of `blend`, or `None` if either theme lacks it.
+    pub fn blend_key(
+        &self,
+        from: &InterpolatableTheme,
+        to: &InterpolatableTheme,
+        key: &str,
+        progress: f32,
+    ) -> Option<Color> {
+        lerp_key_in(
+            from,
+            to,
+            key,
+            self.easing.apply(progress),
+            self.alpha_mode,
+            self.color_space,
+        )
+    }
+}
+
+/// A fade between two themes over an interval of the scheduler's clock.
+#[derive(Clone)]
+pub struct FadeSpan {
+    pub from: InterpolatableTheme,
+    pub to: InterpolatableTheme,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub style: FadeStyle,
+}
+
+impl FadeSpan {
+    /// How far through the fade `now` is, from 0.0 to 1.0.
+    pub fn progress_at(&self, now: DateTime<Utc>) -> f32 {
+        let total_ms = (self.end - self.start).num_milliseconds();
+        if total_ms <= 0 {
+            return 1.0;
+        }
+        let elapsed_ms = (now - self.start).num_milliseconds();
+        (elapsed_ms as f32 / total_ms as f32).clamp(0.0, 1.0)
+    }
+
+    pub fn theme_at(&self, progress: f32) -> InterpolatableTheme {
+        self.style.blend(&self.from, &self.to, progress)
+    }
+}
+
+/// What actually travels down the event channel. Theme frames are not queued:
+/// the newest one waits in a shared slot and only a wake-up is sent.
+enum Delivery {
+    ThemeReady,
+    Control(SchedulerEvent),
+}
+
+/// Creates the channel a `ThemeScheduler` reports through. Theme frames are
+/// coalesced, so a slow UI only ever sees the newest one, while `StateChange`
+/// and `Finished` are never dropped and arrive in the order they were sent.
+pub fn event_channel() -> (EventSender, EventReceiver) {
+    let (deliveries, receiver) = mpsc::unbounded();
+    let latest_theme = Arc::new(Mutex::new(None));
+    (
+        EventSender {
+            deliveries,
+            latest_theme: latest_theme.clone(),
+        },
+        EventReceiver {
+            deliveries: receiver,
+            latest_theme,
+        },
+    )
+}
+
+/// The receiver has been dropped; nobody is listening any more.
+#[derive(Debug)]
+pub struct Disconnected;
+
+pub struct EventSender {
+    deliveries: mpsc::UnboundedSender<Delivery>,
+    latest_theme: Arc<Mutex<Option<InterpolatableTheme>>>,
+}
+
+impl EventSender {
+    pub fn send(&self, event: SchedulerEvent) -> Result<(), Disconnected> {
+        match event {
+            SchedulerEvent::ThemeUpdate(theme) => {
+                // A wake-up is only needed when the slot was empty; otherwise one
+                // is already queued and will pick up this newer frame instead.
+                let was_empty = self.latest_theme.lock().unwrap().replace(theme).is_none();
+                if was_empty {
+                    self.deliveries
+                        .unbounded_send(Delivery::ThemeReady)
+                        .map_err(|_| Disconnected)
+                } else if self.deliveries.is_closed() {
+                    Err(Disconnected)
+                } else {
+                    Ok(())
+                }
+            }
+            event => self
+                .deliveries
+                .unbounded_send(Delivery::Control(event))
+                .map_err(|_| Disconnected),
+        }
+    }
+}
+
+pub struct EventReceiver {
+    deliveries: mpsc::UnboundedReceiver<Delivery>,
+    latest_theme: Arc<Mutex<Option<InterpolatableTheme>>>,
+}
+
+impl EventReceiver {
+    /// The next event, or `None` once the scheduler has exited. A theme frame
+    /// sent before a control event is always delivered before it, though it may
+    /// have been replaced by a newer frame in the meantime.
+    pub async fn next(&mut self) -> Option<SchedulerEvent> {
+        loop {
+            match self.deliveries.next().await? {
+                Delivery::ThemeReady => {
+                    if let Some(theme) = self.latest_theme.lock().unwrap().take() {
+                        return Some(SchedulerEvent::ThemeUpdate(theme));
+                    }
+                }
+                Delivery::Control(event) => return Some(event),
+            }
+        }
+    }
+}
+
+/// How long a theme picked by hand holds before the schedule takes over again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverrideUntil {
+    /// Until the next scheduled fade, which then starts from the chosen theme.
+    NextEvent,
+    /// For a fixed time, after which the scheduler fades back into the schedule.
+    For(Duration),
+}
+
+impl OverrideUntil {
+    pub fn next(&self) -> Self {
+        match self {
+            Self::NextEvent => Self::For(Duration::hours(1)),
+            Self::For(duration) if *duration < Duration::hours(4) => Self::For(Duration::hours(4)),
+            Self::For(_) => Self::NextEvent,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::NextEvent => "Until Next Event".to_string(),
+            Self::For(duration) => format!("For {}h", duration.num_hours()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum SchedulerCommand {
+    Pause,
+    Resume,
+    /// Start the next transition now instead of waiting for it; during a fade,
+    /// finish the fade.
+    SkipToNext,
+    /// Apply the next transition's end theme immediately, without fading.
+    JumpToEnd,
+    /// Show `theme` instead of the schedule until `until` says otherwise.
+    Override {
+        theme: InterpolatableTheme,
+        label: ThemeLabel,
+        until: OverrideUntil,
+    },
+    /// Replace the schedule, fading over `fade` from what is on screen into
+    /// the new one.
+    SetSchedule {
+        schedule: Arc<Vec<ScheduleEntry>>,
+        fade: Duration,
+    },
+    Cancel,
+}
+
+static NEXT_HANDLE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Controls a running `ThemeScheduler`. Dropping the handle cancels the
+/// scheduler, so whoever owns the handle owns the thread's lifetime.
+pub struct SchedulerHandle {
+    id: usize,
+    commands: std_mpsc::Sender<SchedulerCommand>,
+    clock: Arc<dyn Clock>,
+}
+
+impl SchedulerHandle {
+    /// Distinguishes this run from earlier ones, so stale events can be ignored.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// The clock the scheduler runs on, for placing its schedule in time.
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    pub fn pause(&self) {
+        self.send(SchedulerCommand::Pause);
+    }
+
+    pub fn resume(&self) {
+        self.send(SchedulerCommand::Resume);
+    }
+
+    pub fn skip_to_next(&self) {
+        self.send(SchedulerCommand::SkipToNext);
+    }
+
+    pub fn jump_to_end(&self) {
+        self.send(SchedulerCommand::JumpToEnd);
+    }
+
+    pub fn override_theme(&self, theme: &Theme, until: OverrideUntil) {
+        self.send(SchedulerCommand::Override {
+            theme: theme.interpolatable_theme.clone(),
+            label: theme.label(),
+            until,
+        });
+    }
+
+    pub fn set_schedule(&self, schedule: Arc<Vec<ScheduleEntry>>) {
+        let fade = Duration::from_std(HANDOVER_FADE).unwrap_or(Duration::zero());
+        self.set_schedule_with_fade(schedule, fade);
+    }
+
+    pub fn set_schedule_with_fade(&self, schedule: Arc<Vec<ScheduleEntry>>, fade: Duration) {
+        self.send(SchedulerCommand::SetSchedule { schedule, fade });
+    }
+
+    pub fn cancel(&self) {
+        self.send(SchedulerCommand::Cancel);
+    }
+
+    pub fn send(&self, command: SchedulerCommand) {
+        // The thread may already have finished; there is nothing left to control.
+        let _ = self.commands.send(command);
+    }
+}
+
+/// What a control command (or the clock) asks the current phase of the loop to do.
+enum Interrupt {
+    SkipToNext,
+    JumpToEnd,
+    /// A theme was picked by hand; it waits in `ThemeScheduler::pending_override`.
+    Override,
+    /// A new schedule waits in `ThemeScheduler::pending_schedule`.
+    ScheduleChanged,
+    Cancel,
+    /// The clock moved much further than the loop waited for, e.g. the machine
+    /// was suspended or the time was set. Only raised for real schedules.
+    ClockJump,
+}
+
+/// How a fade ended.
+enum FadeOutcome {
+    Completed,
+    Cancelled,
+    ClockJumped,
+    Overridden,
+    ScheduleChanged,
+}
+
+/// What the scheduler last put on screen, kept cheaply so a catch-up fade can
+/// start from it.
+enum Shown {
+    Theme(InterpolatableTheme),
+    Fade {
+        from: InterpolatableTheme,
+        to: InterpolatableTheme,
+        progress: f32,
+        style: FadeStyle,
+    },
+}
+
+impl Shown {
+    fn into_theme(self) -> InterpolatableTheme {
+        match self {
+            Self::Theme(theme) => theme,
+            Self::Fade {
+                from,
+                to,
+                progress,
+                style,
+            } => style.blend(&from, &to, progress),
+        }
+    }
+}
+
 // --- THEME SCHEDULER SERVICE ---
 
 pub struct ThemeScheduler {
     schedule: Arc<Vec<ScheduleEntry>>,
-    event_sender: mpsc::Sender<SchedulerEvent>,
+    event_sender: EventSender,
+    commands: std_mpsc::Receiver<SchedulerCommand>,
     app_mode: AppMode,
+    clock: Arc<dyn Clock>,
+    fade_driver: FadeDriver,
+    catch_up: CatchUp,
+    // Time spent paused during an interactive simulation. It is subtracted from
+    // the clock so the simulation resumes where it left off; a real schedule
+    // instead catches up with the clock.
+    paused_offset: Duration,
+    receiver_dropped: bool,
+    shown: Option<Shown>,
+    // A manual theme choice not yet acted on, and whether one is on screen while
+    // waiting for the next fade (`OverrideUntil::NextEvent`).
+    pending_override: Option<(InterpolatableTheme, ThemeLabel, OverrideUntil)>,
+    override_active: bool,
+    // A schedule to switch to, applied at the top of the loop so no index into
+    // the old one outlives it.
+    pending_schedule: Option<(Arc<Vec<ScheduleEntry>>, Duration)>,
 }
 
-#[derive(Clone)]
+/// Real time between frames while fading (~60 fps).
+const FRAME_INTERVAL: StdDuration = StdDuration::from_millis(16);
+/// How far (in real time) a wait may overshoot or undershoot before it counts
+/// as a clock jump rather than scheduling jitter.
+const CLOCK_JUMP_TOLERANCE: StdDuration = StdDuration::from_secs(2);
+/// The longest the scheduler sleeps between checks during a long fade or while
+/// holding a single entry, so a suspend is noticed part way through.
+const MAX_IDLE_WAIT: StdDuration = StdDuration::from_secs(60);
+/// How long the fade back into the schedule takes when a timed override ends,
+/// or into a new schedule when it is switched.
+const HANDOVER_FADE: StdDuration = StdDuration::from_secs(5);
+
+/// How long until the countdown shown for `remaining` changes: each second under
+/// two minutes, then on each minute boundary. Minutes also cover the hours of
+/// `SimulationState::display` and the hours and minutes of the dashboard, and
+/// waking at least once a minute notices timezone changes hours before the next
+/// event.
+fn countdown_tick(remaining: Duration) -> Duration {
+    let unit = if remaining <= Duration::minutes(2) {
+        Duration::seconds(1)
+    } else {
+        Duration::minutes(1)
+    };
+    let unit_ms = unit.num_milliseconds();
+    let remaining_ms = remaining.num_milliseconds();
+    let until_change_ms = match remaining_ms % unit_ms {
+        0 => unit_ms,
+        partial => partial,
+    };
+    Duration::milliseconds(until_change_ms)
+}
+
+/// The timezone a schedule entry's wall-clock times are read in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScheduleZone {
+    /// Whatever the system timezone is at the moment; follows the user when they travel.
+    #[default]
+    SystemLocal,
+    /// An explicit IANA timezone, e.g. `Europe/Berlin`.
+    Named(Tz),
+}
+
+impl ScheduleZone {
+    pub fn parse(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("local") || name.eq_ignore_ascii_case("system") {
+            Some(Self::SystemLocal)
+        } else {
+            name.parse::<Tz>().ok().map(Self::Named)
+        }
+    }
+
+    /// The calendar date `instant` falls on in this zone.
+    pub fn date_of(&self, instant: DateTime<Utc>) -> NaiveDate {
+        match self {
+            Self::SystemLocal => instant.with_timezone(&Local).date_naive(),
+            Self::Named(tz) => instant.with_timezone(tz).date_naive(),
+        }
+    }
+
+    /// The zone's UTC offset at `instant`, in seconds.
+    pub fn utc_offset_seconds(&self, instant: DateTime<Utc>) -> i32 {
+        match self {
+            Self::SystemLocal => instant.with_timezone(&Local).offset().fix().local_minus_utc(),
+            Self::Named(tz) => instant.with_timezone(tz).offset().fix().local_minus_utc(),
+        }
+    }
+
+    /// Resolves a wall-clock time in this zone to an absolute instant.
+    ///
+    /// A time repeated by a DST fall-back resolves to its first occurrence, so
+    /// it fires once. A time skipped by a spring-forward resolves using the
+    /// offset from before the gap, i.e. as far past the jump as it was into it
+    /// (02:30 becomes 03:30).
+    pub fn resolve(&self, naive: NaiveDateTime) -> DateTime<Utc> {
+        match self {
+            Self::SystemLocal => resolve_in(&Local, naive),
+            Self::Named(tz) => resolve_in(tz, naive),
+        }
+    }
+}
+
+impl fmt::Display for ScheduleZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SystemLocal => f.write_str("local"),
+            Self::Named(tz) => f.write_str(tz.name()),
+        }
+    }
+}
+
+fn resolve_in<Z: TimeZone>(zone: &Z, naive: NaiveDateTime) -> DateTime<Utc> {
+    match zone.from_local_datetime(&naive) {
+        LocalResult::Single(instant) => instant.with_timezone(&Utc),
+        LocalResult::Ambiguous(earliest, _) => earliest.with_timezone(&Utc),
+        LocalResult::None => {
+            // No DST gap is longer than a few hours, so this is safely before it.
+            let offset_before_gap = zone
+                .offset_from_utc_datetime(&(naive - Duration::hours(6)))
+                .fix()
+                .local_minus_utc();
+            (naive - Duration::seconds(offset_before_gap as i64)).and_utc()
+        }
+    }
+}
+
+/// When a schedule entry takes effect each day.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScheduleTime {
+    /// A fixed wall-clock time in the entry's zone.
+    Fixed(NaiveTime),
+    /// A solar event at `location`, shifted by `offset` (negative means earlier).
+    Solar {
+        event: SolarEvent,
+        offset: Duration,
+        location: GeoLocation,
+    },
+}
+
+impl ScheduleTime {
+    /// Parses `HH:MM`, `HH:MM:SS`, or a solar event name with an optional
+    /// `+HH:MM`/`-HH:MM` offset such as `sunset-00:30`. Solar times need a
+    /// `location`.
+    pub fn parse(text: &str, location: Option<GeoLocation>) -> Result<Self, String> {
+        let text = text.trim();
+        if text.starts_with(|c: char| c.is_ascii_digit()) {
+            return NaiveTime::parse_from_str(text, "%H:%M:%S")
+                .or_else(|_| NaiveTime::parse_from_str(text, "%H:%M"))
+                .map(Self::Fixed)
+                .map_err(|_| format!("\"{}\" is not a valid HH:MM or HH:MM:SS time", text));
+        }
+
+        let (name, offset) = match text.find(['+', '-']) {
+            Some(split) => {
+                let (name, offset) = text.split_at(split);
+                let sign = if offset.starts_with('-') { -1 } else { 1 };
+                let offset = NaiveTime::parse_from_str(&offset[1..], "%H:%M")
+                    .map_err(|_| format!("\"{}\" is not a valid HH:MM offset", &offset[1..]))?;
+                let minutes = offset.signed_duration_since(NaiveTime::MIN).num_minutes();
+                let offset = Duration::try_minutes(sign * minutes)
+                    .ok_or_else(|| format!("\"{}\" is too large an offset", &text[split + 1..]))?;
+                (name, offset)
+            }
+            None => (text, Duration::zero()),
+        };
+        let event = SolarEvent::from_name(name).ok_or_else(|| {
+            format!(
+                "\"{}\" is neither a time nor a solar event (sunrise, sunset, civil_dawn, \
+                 civil_dusk, nautical_dawn, nautical_dusk)",
+                name
+            )
+        })?;
+        let location =
+            location.ok_or_else(|| format!("\"{}\" needs a location to be set", text))?;
+        Ok(Self::Solar {
+            event,
+            offset,
+            location,
+        })
+    }
+
+    /// The instant this time falls on `date` in `zone`, or `None` for a solar
+    /// event that doesn't happen that day (e.g. no sunset during a polar summer).
+    pub fn on(&self, date: NaiveDate, zone: ScheduleZone) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Fixed(time) => Some(zone.resolve(date.and_time(*time))),
+            Self::Solar {
+                event,
+                offset,
+                location,
+            } => solar_event_time(*event, date, *location)
+                .and_then(|instant| instant.checked_add_signed(*offset)),
+        }
+    }
+}
+
+impl From<NaiveTime> for ScheduleTime {
+    fn from(time: NaiveTime) -> Self {
+        Self::Fixed(time)
+    }
+}
+
+impl fmt::Display for ScheduleTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fixed(time) => write!(f, "{}", time.format("%H:%M:%S")),
+            Self::Solar { event, offset, .. } if *offset == Duration::zero() => write!(f, "{}", event),
+            Self::Solar { event, offset, .. } => {
+                let sign = if *offset < Duration::zero() { '-' } else { '+' };
+                let minutes = offset.num_minutes().abs();
+                write!(f, "{}{}{:02}:{:02}", event, sign, minutes / 60, minutes % 60)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct ScheduleEntry {
-    pub time: NaiveTime,
+    pub time: ScheduleTime,
+    pub zone: ScheduleZone,
     pub theme: InterpolatableTheme,
+    /// Which theme `theme` is.
+    pub label: ThemeLabel,
     pub fade_duration: Duration,
+    pub style: FadeStyle,
+    /// The days (in `zone`) this entry takes effect on.
+    pub days: ScheduleDays,
+}
+
+impl ScheduleEntry {
+    /// The instant this entry takes effect on `date`, if it does that day.
+    pub fn occurs_on(&self, date: NaiveDate) -> Option<DateTime<Utc>> {
+        if self.days.contains(date) {
+            self.time.on(date, self.zone)
+        } else {
+            None
+        }
+    }
+}
+
+/// A set of weekdays.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DaySet(u8);
+
+impl DaySet {
+    pub const ALL: Self = Self(0b111_1111);
+
+    pub fn contains(&self, day: Weekday) -> bool {
+        self.0 & Self::bit(day) != 0
+    }
+
+    fn bit(day: Weekday) -> u8 {
+        1 << day.num_days_from_monday()
+    }
+}
+
+impl Default for DaySet {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl FromIterator<Weekday> for DaySet {
+    fn from_iter<I: IntoIterator<Item = Weekday>>(days: I) -> Self {
+        Self(days.into_iter().fold(0, |bits, day| bits | Self::bit(day)))
+    }
+}
+
+/// An inclusive range of calendar dates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        (self.start..=self.end).contains(&date)
+    }
+}
+
+/// Days on which a set of entries replaces the default ones: certain weekdays,
+/// and optionally only within certain dates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayVariant {
+    pub days: DaySet,
+    /// Empty means any date.
+    pub dates: Vec<DateRange>,
+}
+
+impl DayVariant {
+    pub fn matches(&self, date: NaiveDate) -> bool {
+        self.days.contains(date.weekday())
+            && (self.dates.is_empty() || self.dates.iter().any(|range| range.contains(date)))
+    }
+
+    fn is_date_exception(&self) -> bool {
+        !self.dates.is_empty()
+    }
+}
+
+/// Decides which variant of a schedule applies on each day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DayPlan {
+    pub variants: Vec<DayVariant>,
+}
+
+impl DayPlan {
+    /// The variant in force on `date`, or `None` for the default entries.
+    /// Date exceptions (holidays, demo days) win over weekday variants, which
+    /// win over the defaults; within each tier the first listed wins.
+    pub fn variant_for(&self, date: NaiveDate) -> Option<usize> {
+        let first_match = |exceptions: bool| {
+            self.variants.iter().position(|variant| {
+                variant.is_date_exception() == exceptions && variant.matches(date)
+            })
+        };
+        first_match(true).or_else(|| first_match(false))
+    }
+}
+
+/// The days an entry takes effect on: those where its variant (or, for
+/// `variant: None`, no variant) is the one the plan picks.
+#[derive(Clone, Debug, Default)]
+pub struct ScheduleDays {
+    pub plan: Arc<DayPlan>,
+    pub variant: Option<usize>,
+}
+
+impl ScheduleDays {
+    pub fn every_day() -> Self {
+        Self::default()
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.plan.variant_for(date) == self.variant
+    }
 }
 
 impl ThemeScheduler {
     pub fn spawn(
-        event_sender: mpsc::Sender<SchedulerEvent>,
+        event_sender: EventSender,
         schedule: Arc<Vec<ScheduleEntry>>,
         app_mode: AppMode,
-    ) {
+        clock: Arc<dyn Clock>,
+        fade_driver: FadeDriver,
+        catch_up: CatchUp,
+    ) -> SchedulerHandle {
+        let (command_sender, commands) = std_mpsc::channel();
+        let handle_clock = clock.clone();
         let mut scheduler = Self {
             schedule,
             event_sender,
+            commands,
             app_mode,
+            clock,
+            fade_driver,
+            catch_up,
+            paused_offset: Duration::zero(),
+            receiver_dropped: false,
+            shown: None,
+            pending_override: None,
+            override_active: false,
+            pending_schedule: None,
         };
         thread::spawn(move || {
             info!("ThemeScheduler: Background thread spawned.");
             scheduler.run_loop();
+            info!("ThemeScheduler: Background thread exiting.");
         });
+        SchedulerHandle {
+            id: NEXT_HANDLE_ID.fetch_add(1, Ordering::Relaxed),
+            commands: command_sender,
+            clock: handle_clock,
+        }
+    }
+
+    /// The schedule's notion of "now": the clock, minus any paused time.
+    fn now(&self) -> DateTime<Utc> {
+        self.clock.now() - self.paused_offset
     }
 
     fn run_loop(&mut self) {
-        let mut current_theme_idx = find_previous_event_index(Local::now().time(), &self.schedule);
+        let now = self.now();
+        let Some(sample) = theme_at(&self.schedule, now) else {
+            error!("ThemeScheduler: No schedule entry ever occurs. Exiting thread.");
+            return;
+        };
+        let mut current_theme_idx = sample.current_index;
+        let mut current_theme = self.schedule[current_theme_idx].theme.clone();
+        // The absolute instant the current entry last took effect. Every later
+        // event is placed relative to this, so fades that straddle midnight and
+        // the wrap from the last entry back to the first keep their real dates.
+        let mut anchor = sample.current_at;
+        // A fade in progress is picked up by the loop below; otherwise show the
+        // current entry straight away rather than waiting for the next fade. A
+        // single entry never fades (see below), so it is always shown.
+        if !sample.is_fading() || self.schedule.len() == 1 {
+            let label = self.schedule[sample.current_index].label.clone();
+            self.show(sample.theme, label);
+        }
 
-        loop {
-            let now = Local::now().time();
-            let prev_event = &self.schedule[current_theme_idx];
-            let next_event_idx = (current_theme_idx + 1) % self.schedule.len();
+        'schedule: loop {
+            if let Some((theme, label, until)) = self.pending_override.take() {
+                let Some((index, at, theme)) = self.hold_override(theme, label, until) else {
+                    return;
+                };
+                current_theme_idx = index;
+                current_theme = theme;
+                anchor = at;
+            }
+            if let Some((schedule, fade)) = self.pending_schedule.take() {
+                info!(
+                    "ThemeScheduler: Switching to a schedule with {} entries.",
+                    schedule.len()
+                );
+                self.schedule = schedule;
+                let Some(sample) = self.fade_into_schedule(fade) else {
+                    return;
+                };
+                current_theme_idx = sample.current_index;
+                current_theme = self.schedule[current_theme_idx].theme.clone();
+                anchor = sample.current_at;
+            }
+            // A single entry only ever fades into itself. Holding it instead
+            // keeps its daily recurrence from re-announcing a theme that is
+            // already on screen, which would re-run hooks and rewrite settings.
+            if self.schedule.len() == 1 && !self.override_active {
+                self.dispatch_event(SchedulerEvent::StateChange(SimulationState::Idle));
+                loop {
+                    match self.wait_for(MAX_IDLE_WAIT) {
+                        Some(Interrupt::Cancel) => return,
+                        Some(Interrupt::Override | Interrupt::ScheduleChanged) => {
+                            continue 'schedule
+                        }
+                        None
+                        | Some(
+                            Interrupt::SkipToNext | Interrupt::JumpToEnd | Interrupt::ClockJump,
+                        ) => {}
+                    }
+                }
+            }
+            let Some((next_event_idx, fade_end_time)) =
+                next_reachable(&self.schedule, current_theme_idx, anchor)
+            else {
+                error!("ThemeScheduler: No schedule entry ever occurs. Exiting thread.");
+                return;
+            };
             let next_event = self.schedule[next_event_idx].clone();
-
-            let current_theme = prev_event.theme.clone();
-
-            let fade_start_time = next_event.time - next_event.fade_duration;
-            let fade_end_time = next_event.time;
+            // The window actually faded over. Skip/jump commands pull it forward,
+            // while `fade_end_time` stays the scheduled occurrence to plan from.
+            let mut fade_start_time = fade_end_time - next_event.fade_duration;
+            let mut fade_finish_time = fade_end_time;
+            // The plan above was made with these offsets; if either changes while we
+            // sleep (the user changes timezone, or tzdata disagrees with our guess),
+            // the wall-clock time may now map to a different instant.
+            let planned_offsets = (
+                next_event.zone.utc_offset_seconds(fade_end_time),
+                ScheduleZone::SystemLocal.utc_offset_seconds(self.clock.now()),
+            );
 
             info!(
                 "ThemeScheduler: Next event is at {}. Fading from {}.",
-                fade_end_time, fade_start_time
+                fade_end_time.with_timezone(&Local),
+                fade_start_time.with_timezone(&Local)
             );
 
-            if now < fade_start_time {
-                // Sleep Phase
-                loop {
-                    let now = Local::now().time();
-                    if now >= fade_start_time {
-                        break;
+            // Sleep Phase
+            loop {
+                let now = self.now();
+                if now >= fade_start_time {
+                    break;
+                }
+                let current_offsets = (
+                    next_event.zone.utc_offset_seconds(fade_end_time),
+                    ScheduleZone::SystemLocal.utc_offset_seconds(self.clock.now()),
+                );
+                if current_offsets != planned_offsets {
+                    info!("ThemeScheduler: UTC offset changed. Rescheduling.");
+                    continue 'schedule;
+                }
+                let remaining_ms = (fade_start_time - now).num_milliseconds();
+                let seconds = (remaining_ms as f32 / 1000.0).ceil() as usize;
+
+                // Dispatch status update
+                self.dispatch_event(SchedulerEvent::StateChange(if self.override_active {
+                    SimulationState::Overridden {
+                        seconds_remaining: seconds,
                     }
-                    let remaining_ms = (fade_start_time - now).num_milliseconds();
-                    let seconds = (remaining_ms as f32 / 1000.0).ceil() as usize;
-                    
-                    // Dispatch status update
-                    self.dispatch_event(SchedulerEvent::StateChange(SimulationState::Sleeping {
+                } else {
+                    SimulationState::Sleeping {
                         seconds_remaining: seconds,
-                    }));
+                    }
+                }));
+
+                // Sleep until the countdown changes, the fade starts, or a command arrives.
+                let wait = countdown_tick(fade_start_time - now)
+                    .min(fade_start_time - now)
+                    .to_std()
+                    .unwrap_or_default();
 
-                    // Sleep a bit (e.g. 100ms)
-                    thread::sleep(StdDuration::from_millis(100));
+                match self.wait_for(wait) {
+                    None => {}
+                    Some(Interrupt::Cancel) => return,
+                    Some(Interrupt::SkipToNext) => {
+                        info!("ThemeScheduler: Skipping ahead to the next fade.");
+                        let now = self.now();
+                        fade_start_time = now;
+                        fade_finish_time = now + next_event.fade_duration;
+                        break;
+                    }
+                    Some(Interrupt::JumpToEnd) => {
+                        info!("ThemeScheduler: Jumping to the end of the next fade.");
+                        fade_finish_time = self.now();
+                        break;
+                    }
+                    Some(Interrupt::ClockJump) => {
+                        let Some(sample) = self.catch_up_with_schedule() else {
+                            return;
+                        };
+                        current_theme_idx = sample.current_index;
+                        current_theme = self.schedule[current_theme_idx].theme.clone();
+                        anchor = sample.current_at;
+                        continue 'schedule;
+                    }
+                    Some(Interrupt::Override | Interrupt::ScheduleChanged) => continue 'schedule,
                 }
             }
-            
+
             // Double check we are ready to fade
-            let now = Local::now().time();
-            if now < fade_end_time {
+            let now = self.now();
+            // The next fade has started from the overriding theme, so the schedule is back in charge.
+            self.override_active = false;
+            let start_theme = std::mem::replace(&mut current_theme, next_event.theme.clone());
+            current_theme_idx = next_event_idx;
+            anchor = fade_end_time;
+            if now < fade_finish_time {
                 info!("ThemeScheduler: Starting fade...");
-                self.run_fade_loop(&current_theme, &next_event);
-                current_theme_idx = next_event_idx;
+                match self.run_fade_loop(
+                    &start_theme,
+                    &next_event,
+                    fade_start_time,
+                    fade_finish_time,
+                ) {
+                    FadeOutcome::Completed => {}
+                    FadeOutcome::Cancelled => return,
+                    FadeOutcome::ClockJumped => {
+                        let Some(sample) = self.catch_up_with_schedule() else {
+                            return;
+                        };
+                        current_theme_idx = sample.current_index;
+                        current_theme = self.schedule[current_theme_idx].theme.clone();
+                        anchor = sample.current_at;
+                        continue 'schedule;
+                    }
+                    FadeOutcome::Overridden | FadeOutcome::ScheduleChanged => continue 'schedule,
+                }
 
                 if self.app_mode == AppMode::Interactive {
                     info!("ThemeScheduler: Interactive simulation complete. Exiting thread.");
@@ -102,11 +1035,11 @@ impl ThemeScheduler {
             } else {
                 // We missed the window or it's time to set final
                 info!("ThemeScheduler: Setting final theme and finding next event.");
-                self.dispatch_event(SchedulerEvent::ThemeUpdate(next_event.theme.clone()));
-                current_theme_idx = next_event_idx;
-                
-                // Small delay to avoid tight loop if logic is off
-                thread::sleep(StdDuration::from_millis(100));
+                self.show(next_event.theme.clone(), next_event.label.clone());
+
+                if let Some(Interrupt::Cancel) = self.poll_commands() {
+                    return;
+                }
 
                 if self.app_mode == AppMode::Interactive {
                     info!("ThemeScheduler: Interactive simulation complete (after catch-up). Exiting thread.");
@@ -118,46 +1051,833 @@ impl ThemeScheduler {
         }
     }
 
-    fn run_fade_loop(&mut self, start_theme: &InterpolatableTheme, target_event: &ScheduleEntry) {
-        let fade_start_time = target_event.time - target_event.fade_duration;
-        let fade_end_time = target_event.time;
-        let total_duration_ms = target_event.fade_duration.num_milliseconds() as f32;
+    /// Fades towards `target_event` until `fade_end_time`.
+    fn run_fade_loop(
+        &mut self,
+        start_theme: &InterpolatableTheme,
+        target_event: &ScheduleEntry,
+        fade_start_time: DateTime<Utc>,
+        fade_end_time: DateTime<Utc>,
+    ) -> FadeOutcome {
+        if self.fade_driver == FadeDriver::Frames {
+            return self.run_frame_synced_fade(
+                start_theme,
+                target_event,
+                fade_start_time,
+                fade_end_time,
+            );
+        }
+        let total_duration_ms = (fade_end_time - fade_start_time).num_milliseconds() as f32;
+        self.shown = Some(Shown::Fade {
+            from: start_theme.clone(),
+            to: target_event.theme.clone(),
+            progress: 0.0,
+            style: target_event.style,
+        });
+        let elapsed_ms = (self.now() - fade_start_time).num_milliseconds() as f32;
+        self.announce(
+            TransitionKind::FadeStarted,
+            &target_event.label,
+            (elapsed_ms / total_duration_ms).clamp(0.0, 1.0),
+        );
 
         loop {
-            let now = Local::now().time();
+            let now = self.now();
             if now >= fade_end_time {
                 break;
             }
             let elapsed_ms = (now - fade_start_time).num_milliseconds() as f32;
             let t = (elapsed_ms / total_duration_ms).clamp(0.0, 1.0);
 
-            let interpolated_theme = lerp_theme(start_theme, &target_event.theme, t);
-            
+            let interpolated_theme = target_event
+                .style
+                .blend(start_theme, &target_event.theme, t);
+            self.set_shown_progress(t);
+
             // Update Theme
             self.dispatch_event(SchedulerEvent::ThemeUpdate(interpolated_theme));
             // Update Status
-            self.dispatch_event(SchedulerEvent::StateChange(SimulationState::Fading { progress: t }));
+            self.dispatch_event(SchedulerEvent::StateChange(SimulationState::Fading {
+                progress: t,
+            }));
+
+            match self.wait_for(FRAME_INTERVAL.mul_f64(self.clock.rate())) {
+                None => {}
+                Some(Interrupt::Cancel) => return FadeOutcome::Cancelled,
+                Some(Interrupt::SkipToNext | Interrupt::JumpToEnd) => {
+                    info!("ThemeScheduler: Finishing fade early.");
+                    break;
+                }
+                Some(Interrupt::ClockJump) => return FadeOutcome::ClockJumped,
+                Some(Interrupt::Override) => return FadeOutcome::Overridden,
+                Some(Interrupt::ScheduleChanged) => return FadeOutcome::ScheduleChanged,
+            }
+        }
+        info!("ThemeScheduler: Fade complete. Setting final theme.");
+        self.announce(TransitionKind::FadeFinished, &target_event.label, 1.0);
+        self.show(target_event.theme.clone(), target_event.label.clone());
+        self.dispatch_event(SchedulerEvent::StateChange(SimulationState::Fading {
+            progress: 1.0,
+        }));
+        FadeOutcome::Completed
+    }
+
+    /// Like `run_fade_loop`, but leaves the intermediate frames to the UI and
+    /// only sleeps until the fade is due to end.
+    fn run_frame_synced_fade(
+        &mut self,
+        start_theme: &InterpolatableTheme,
+        target_event: &ScheduleEntry,
+        fade_start_time: DateTime<Utc>,
+        fade_end_time: DateTime<Utc>,
+    ) -> FadeOutcome {
+        // `None` forces the first announcement; after that, only a pause (which
+        // shifts the schedule against the clock) needs a new one.
+        let mut announced_offset = None;
+        let span = FadeSpan {
+            from: start_theme.clone(),
+            to: target_event.theme.clone(),
+            start: fade_start_time,
+            end: fade_end_time,
+            style: target_event.style,
+        };
+        self.shown = Some(Shown::Fade {
+            from: span.from.clone(),
+            to: span.to.clone(),
+            progress: 0.0,
+            style: span.style,
+        });
+        self.announce(
+            TransitionKind::FadeStarted,
+            &target_event.label,
+            span.progress_at(self.now()),
+        );
+
+        loop {
+            let now = self.now();
+            if now >= fade_end_time {
+                break;
+            }
+            if announced_offset != Some(self.paused_offset) {
+                announced_offset = Some(self.paused_offset);
+                // The UI reads the clock directly, so send clock instants.
+                self.dispatch_event(SchedulerEvent::FadeStarted(FadeSpan {
+                    start: span.start + self.paused_offset,
+                    end: span.end + self.paused_offset,
+                    ..span.clone()
+                }));
+            }
+            // Roughly what the UI is showing, should the clock jump during the wait.
+            self.set_shown_progress(span.progress_at(now));
 
-            thread::sleep(StdDuration::from_millis(16));
+            // Wake periodically all the same, so a suspend is noticed mid-fade.
+            let remaining = (fade_end_time - now)
+                .to_std()
+                .unwrap_or_default()
+                .min(MAX_IDLE_WAIT);
+            match self.wait_for(remaining) {
+                None => {}
+                Some(Interrupt::Cancel) => return FadeOutcome::Cancelled,
+                Some(Interrupt::SkipToNext | Interrupt::JumpToEnd) => {
+                    info!("ThemeScheduler: Finishing fade early.");
+                    break;
+                }
+                Some(Interrupt::ClockJump) => return FadeOutcome::ClockJumped,
+                Some(Interrupt::Override) => return FadeOutcome::Overridden,
+                Some(Interrupt::ScheduleChanged) => return FadeOutcome::ScheduleChanged,
+            }
         }
         info!("ThemeScheduler: Fade complete. Setting final theme.");
-        self.dispatch_event(SchedulerEvent::ThemeUpdate(target_event.theme.clone()));
-        self.dispatch_event(SchedulerEvent::StateChange(SimulationState::Fading { progress: 1.0 }));
+        self.announce(TransitionKind::FadeFinished, &target_event.label, 1.0);
+        self.show(target_event.theme.clone(), target_event.label.clone());
+        self.dispatch_event(SchedulerEvent::StateChange(SimulationState::Fading {
+            progress: 1.0,
+        }));
+        FadeOutcome::Completed
+    }
+
+    /// Shows a theme picked by hand until `until` hands control back, then
+    /// returns where the schedule carries on from: the entry in effect, when it
+    /// took effect, and the theme the next fade should start from. Returns
+    /// `None` if cancelled or if no entry ever occurs.
+    fn hold_override(
+        &mut self,
+        mut theme: InterpolatableTheme,
+        mut label: ThemeLabel,
+        mut until: OverrideUntil,
+    ) -> Option<(usize, DateTime<Utc>, InterpolatableTheme)> {
+        'restart: loop {
+            info!(
+                "ThemeScheduler: Theme overridden {}.",
+                until.label().to_lowercase()
+            );
+            self.show(theme.clone(), label.clone());
+            let started = self.now();
+            let resume_at = match until {
+                OverrideUntil::NextEvent => {
+                    // Sit out any fade in progress too; the one after it then
+                    // starts from the chosen theme instead of the schedule's.
+                    let sample = theme_at(&self.schedule, started)?;
+                    self.override_active = true;
+                    return Some(if sample.is_fading() {
+                        (sample.next_index, sample.next_at, theme)
+                    } else {
+                        (sample.current_index, sample.current_at, theme)
+                    });
+                }
+                OverrideUntil::For(duration) => started + duration,
+            };
+
+            loop {
+                let now = self.now();
+                if now >= resume_at {
+                    break;
+                }
+                let remaining_ms = (resume_at - now).num_milliseconds();
+                self.dispatch_event(SchedulerEvent::StateChange(SimulationState::Overridden {
+                    seconds_remaining: (remaining_ms as f32 / 1000.0).ceil() as usize,
+                }));
+                let wait = countdown_tick(resume_at - now)
+                    .min(resume_at - now)
+                    .to_std()
+                    .unwrap_or_default();
+                match self.wait_for(wait) {
+                    // The hold ends at a fixed instant, so a clock jump needs no
+                    // special handling.
+                    None | Some(Interrupt::ClockJump) => {}
+                    Some(Interrupt::Cancel) => return None,
+                    Some(Interrupt::SkipToNext | Interrupt::JumpToEnd) => break,
+                    // The new schedule is faded into from the overriding theme.
+                    Some(Interrupt::ScheduleChanged) => {
+                        let sample = theme_at(&self.schedule, self.now())?;
+                        return Some((sample.current_index, sample.current_at, theme));
+                    }
+                    Some(Interrupt::Override) => {
+                        if let Some(next) = self.pending_override.take() {
+                            (theme, label, until) = next;
+                        }
+                        continue 'restart;
+                    }
+                }
+            }
+
+            info!("ThemeScheduler: Override ended. Fading back into the schedule.");
+            let duration = Duration::from_std(HANDOVER_FADE).unwrap_or(Duration::zero());
+            let sample = self.fade_into_schedule(duration)?;
+            let current_theme = self.schedule[sample.current_index].theme.clone();
+            return Some((sample.current_index, sample.current_at, current_theme));
+        }
+    }
+
+    /// Catches up after the clock jumped, as configured by `CatchUp`.
+    fn catch_up_with_schedule(&mut self) -> Option<ScheduleSample> {
+        info!("ThemeScheduler: Clock jumped. Catching up with the schedule.");
+        let duration = match self.catch_up {
+            CatchUp::Fade(duration) => duration,
+            CatchUp::Jump => Duration::zero(),
+        };
+        self.fade_into_schedule(duration)
+    }
+
+    /// Fades over `duration` from whatever is on screen to what the schedule
+    /// shows now, then returns the schedule's position so the loop can carry on
+    /// from there. Returns `None` if cancelled or if no entry ever occurs.
+    fn fade_into_schedule(&mut self, duration: Duration) -> Option<ScheduleSample> {
+        self.override_active = false;
+        'restart: loop {
+            let start = self.now();
+            let sample = theme_at(&self.schedule, start)?;
+            let shown = match self.shown.take() {
+                Some(shown) if duration > Duration::zero() => shown,
+                // Nothing to fade from, or no fade wanted: a fade in progress is
+                // continued by the caller, anything else is shown at once.
+                _ => {
+                    if !sample.is_fading() {
+                        let label = self.schedule[sample.current_index].label.clone();
+                        self.show(sample.theme.clone(), label);
+                    }
+                    return Some(sample);
+                }
+            };
+
+            let from = shown.into_theme();
+            self.shown = Some(Shown::Theme(from.clone()));
+            loop {
+                let now = self.now();
+                let elapsed_ms = (now - start).num_milliseconds() as f32;
+                let t = (elapsed_ms / duration.num_milliseconds() as f32).clamp(0.0, 1.0);
+                // The target keeps moving if the schedule is itself mid-fade.
+                let live = theme_at(&self.schedule, now)?;
+                if t >= 1.0 {
+                    if !live.is_fading() {
+                        let label = self.schedule[live.current_index].label.clone();
+                        self.show(live.theme.clone(), label);
+                    }
+                    return Some(live);
+                }
+                let style = self.schedule[live.next_index].style;
+                let frame = style.blend(&from, &live.theme, t);
+                self.dispatch_event(SchedulerEvent::ThemeUpdate(frame));
+                self.dispatch_event(SchedulerEvent::StateChange(SimulationState::Fading {
+                    progress: t,
+                }));
+
+                match self.wait_for(FRAME_INTERVAL.mul_f64(self.clock.rate())) {
+                    None => {}
+                    Some(Interrupt::Cancel) => return None,
+                    Some(Interrupt::SkipToNext | Interrupt::JumpToEnd) => {
+                        let live = theme_at(&self.schedule, self.now())?;
+                        if live.is_fading() {
+                            // The loop carries on with the schedule's own fade,
+                            // which announces the theme once it lands.
+                            self.display(live.theme.clone());
+                        } else {
+                            let label = self.schedule[live.current_index].label.clone();
+                            self.show(live.theme.clone(), label);
+                        }
+                        return Some(live);
+                    }
+                    // The loop takes up the override or new schedule from here.
+                    Some(Interrupt::Override | Interrupt::ScheduleChanged) => {
+                        return theme_at(&self.schedule, self.now());
+                    }
+                    Some(Interrupt::ClockJump) => {
+                        self.shown = Some(Shown::Theme(style.blend(&from, &live.theme, t)));
+                        continue 'restart;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sends `theme` to the UI and remembers it as what is on screen.
+    fn display(&mut self, theme: InterpolatableTheme) {
+        self.shown = Some(Shown::Theme(theme.clone()));
+        self.dispatch_event(SchedulerEvent::ThemeUpdate(theme));
+    }
+
+    /// Displays `theme`, which is `label`'s, and announces it as applied.
+    fn show(&mut self, theme: InterpolatableTheme, label: ThemeLabel) {
+        self.display(theme);
+        self.announce(TransitionKind::ThemeApplied, &label, 1.0);
+    }
+
+    fn announce(&mut self, kind: TransitionKind, theme: &ThemeLabel, progress: f32) {
+        self.dispatch_event(SchedulerEvent::Transition(Transition {
+            kind,
+            theme: theme.clone(),
+            progress,
+        }));
+    }
+
+    fn set_shown_progress(&mut self, t: f32) {
+        if let Some(Shown::Fade { progress, .. }) = &mut self.shown {
+            *progress = t;
+        }
+    }
+
+    /// Waits `duration` of clock time, returning early if a command arrives.
+    /// Pausing blocks here until the scheduler is resumed (or told to do
+    /// something else); callers re-check the time whenever this returns.
+    fn wait_for(&mut self, duration: StdDuration) -> Option<Interrupt> {
+        if self.receiver_dropped {
+            info!("ThemeScheduler: Event receiver dropped. Cancelling.");
+            return Some(Interrupt::Cancel);
+        }
+        let before = self.now();
+        let interrupt = match self.clock.real_duration(duration) {
+            None => {
+                self.clock.sleep(duration);
+                self.poll_commands()
+            }
+            Some(real_duration) => match self.commands.recv_timeout(real_duration) {
+                Ok(command) => self.handle_command(command),
+                Err(std_mpsc::RecvTimeoutError::Timeout) => None,
+                Err(std_mpsc::RecvTimeoutError::Disconnected) => {
+                    info!("ThemeScheduler: Handle dropped. Cancelling.");
+                    Some(Interrupt::Cancel)
+                }
+            },
+        };
+        if interrupt.is_none() && self.app_mode == AppMode::Scheduler {
+            // A command may have ended the wait early, so only overshooting
+            // (suspend, a long pause) or going backwards counts as a jump.
+            let waited = self.now() - before;
+            let expected = Duration::from_std(duration).unwrap_or(Duration::zero());
+            let tolerance = Duration::from_std(CLOCK_JUMP_TOLERANCE.mul_f64(self.clock.rate()))
+                .unwrap_or(Duration::zero());
+            if waited < Duration::zero() || waited > expected + tolerance {
+                return Some(Interrupt::ClockJump);
+            }
+        }
+        interrupt
+    }
+
+    /// Handles any pending control commands without waiting.
+    fn poll_commands(&mut self) -> Option<Interrupt> {
+        if self.receiver_dropped {
+            info!("ThemeScheduler: Event receiver dropped. Cancelling.");
+            return Some(Interrupt::Cancel);
+        }
+        loop {
+            match self.commands.try_recv() {
+                Ok(command) => {
+                    if let Some(interrupt) = self.handle_command(command) {
+                        return Some(interrupt);
+                    }
+                }
+                Err(std_mpsc::TryRecvError::Empty) => return None,
+                Err(std_mpsc::TryRecvError::Disconnected) => {
+                    info!("ThemeScheduler: Handle dropped. Cancelling.");
+                    return Some(Interrupt::Cancel);
+                }
+            }
+        }
+    }
+
+    fn handle_command(&mut self, command: SchedulerCommand) -> Option<Interrupt> {
+        match command {
+            SchedulerCommand::Pause => self.wait_while_paused(),
+            SchedulerCommand::Resume => None,
+            SchedulerCommand::SkipToNext => Some(Interrupt::SkipToNext),
+            SchedulerCommand::JumpToEnd => Some(Interrupt::JumpToEnd),
+            SchedulerCommand::Override {
+                theme,
+                label,
+                until,
+            } => {
+                self.pending_override = Some((theme, label, until));
+                Some(Interrupt::Override)
+            }
+            SchedulerCommand::SetSchedule { schedule, fade } => {
+                self.pending_schedule = Some((schedule, fade));
+                Some(Interrupt::ScheduleChanged)
+            }
+            SchedulerCommand::Cancel => {
+                info!("ThemeScheduler: Cancelled.");
+                Some(Interrupt::Cancel)
+            }
+        }
+    }
+
+    fn wait_while_paused(&mut self) -> Option<Interrupt> {
+        info!("ThemeScheduler: Paused.");
+        let paused_at = self.clock.now();
+        self.dispatch_event(SchedulerEvent::StateChange(SimulationState::Paused));
+
+        let interrupt = loop {
+            match self.commands.recv() {
+                Ok(SchedulerCommand::Resume) => break None,
+                Ok(SchedulerCommand::Pause) => {}
+                Ok(SchedulerCommand::SkipToNext) => break Some(Interrupt::SkipToNext),
+                Ok(SchedulerCommand::JumpToEnd) => break Some(Interrupt::JumpToEnd),
+                Ok(SchedulerCommand::Override {
+                    theme,
+                    label,
+                    until,
+                }) => {
+                    self.pending_override = Some((theme, label, until));
+                    break Some(Interrupt::Override);
+                }
+                Ok(SchedulerCommand::SetSchedule { schedule, fade }) => {
+                    self.pending_schedule = Some((schedule, fade));
+                    break Some(Interrupt::ScheduleChanged);
+                }
+                Ok(SchedulerCommand::Cancel) | Err(_) => {
+                    info!("ThemeScheduler: Cancelled while paused.");
+                    return Some(Interrupt::Cancel);
+                }
+            }
+        };
+
+        if self.app_mode == AppMode::Interactive {
+            self.paused_offset += self.clock.now() - paused_at;
+        }
+        info!("ThemeScheduler: Resumed.");
+        interrupt
     }
 
     fn dispatch_event(&mut self, event: SchedulerEvent) {
-        if let Err(e) = self.event_sender.try_send(event) {
-            tracing::warn!("Failed to send scheduler event: {}", e);
+        if self.event_sender.send(event).is_err() {
+            // Nobody is listening any more (e.g. the window was closed).
+            self.receiver_dropped = true;
         }
     }
 }
 
-pub fn find_previous_event_index(now: NaiveTime, schedule: &[ScheduleEntry]) -> usize {
+/// How many days to search for an occurrence before giving up. A year covers
+/// even the longest polar night.
+const MAX_SEARCH_DAYS: i64 = 366;
+
+/// The first occurrence of `entry` after `after` (or at it, when `inclusive`).
+pub fn next_occurrence(
+    entry: &ScheduleEntry,
+    after: DateTime<Utc>,
+    inclusive: bool,
+) -> Option<DateTime<Utc>> {
+    // Start a day early: a solar event with a large negative offset can land on
+    // the previous calendar day.
+    let first_date = entry.zone.date_of(after) - Duration::days(1);
+    (0..=MAX_SEARCH_DAYS)
+        .filter_map(|day| entry.occurs_on(first_date + Duration::days(day)))
+        .find(|candidate| *candidate > after || (inclusive && *candidate == after))
+}
+
+/// The most recent occurrence of `entry` at or before `now`.
+pub fn previous_occurrence(entry: &ScheduleEntry, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let first_date = entry.zone.date_of(now) + Duration::days(1);
+    (0..=MAX_SEARCH_DAYS)
+        .filter_map(|day| entry.occurs_on(first_date - Duration::days(day)))
+        .find(|candidate| *candidate <= now)
+}
+
+/// The entry that most recently took effect, and when: the latest occurrence of
+/// any entry up to `now`, looking back across midnight if necessary. Entries
+/// that haven't occurred lately, such as those only for a future date, are
+/// passed over; `None` if none has.
+pub fn latest_occurrence(
+    schedule: &[ScheduleEntry],
+    now: DateTime<Utc>,
+) -> Option<(usize, DateTime<Utc>)> {
     schedule
         .iter()
         .enumerate()
-        .filter(|(_, e)| e.time <= now)
-        .last()
-        .map(|(i, _)| i)
-        .unwrap_or(schedule.len() - 1)
-}
\ No newline at end of file
+        .filter_map(|(i, e)| previous_occurrence(e, now).map(|occurrence| (i, occurrence)))
+        // On a tie the earlier entry wins, so a zero-length gap still plays in order.
+        .max_by_key(|(i, occurrence)| (*occurrence, Reverse(*i)))
+}
+
+/// The entry that takes effect next after `current_index` did at `anchor`, and
+/// when. This is the earliest upcoming occurrence of any entry, so entries
+/// limited to certain days, or solar times that swap order with the seasons,
+/// need not be listed in order. Entries sharing an instant take effect in list
+/// order; entries that never occur (e.g. a sunset during polar day) are skipped.
+pub fn next_reachable(
+    schedule: &[ScheduleEntry],
+    current_index: usize,
+    anchor: DateTime<Utc>,
+) -> Option<(usize, DateTime<Utc>)> {
+    schedule
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            next_occurrence(entry, anchor, index > current_index)
+                .map(|occurrence| (index, occurrence))
+        })
+        .min_by_key(|(index, occurrence)| (*occurrence, *index <= current_index, *index))
+}
+
+/// Every occurrence of every entry in `[from, until)`, in the order they take effect.
+pub fn occurrences_between(
+    schedule: &[ScheduleEntry],
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Vec<(usize, DateTime<Utc>)> {
+    let mut occurrences = Vec::new();
+    for (index, entry) in schedule.iter().enumerate() {
+        let mut next = next_occurrence(entry, from, true);
+        while let Some(at) = next.filter(|at| *at < until) {
+            occurrences.push((index, at));
+            next = next_occurrence(entry, at, false);
+        }
+    }
+    occurrences.sort_by_key(|(index, at)| (*at, *index));
+    occurrences
+}
+
+/// What a schedule shows at one instant, as computed by `theme_at`.
+#[derive(Clone)]
+pub struct ScheduleSample {
+    /// The entry that most recently took effect, and when.
+    pub current_index: usize,
+    pub current_at: DateTime<Utc>,
+    /// The entry that takes effect next, and when its fade ends.
+    pub next_index: usize,
+    pub next_at: DateTime<Utc>,
+    /// When the fade into `next_index` begins.
+    pub fade_start: DateTime<Utc>,
+    /// How far through that fade the instant is; 0.0 before it begins.
+    pub progress: f32,
+    pub theme: InterpolatableTheme,
+}
+
+impl ScheduleSample {
+    pub fn is_fading(&self) -> bool {
+        self.progress > 0.0
+    }
+}
+
+/// The exact theme `schedule` shows at `instant`, without running a scheduler.
+/// Returns `None` for an empty schedule or one whose entries never occur.
+pub fn theme_at(schedule: &[ScheduleEntry], instant: DateTime<Utc>) -> Option<ScheduleSample> {
+    let (current_index, current_at) = latest_occurrence(schedule, instant)?;
+    let (next_index, next_at) = next_reachable(schedule, current_index, current_at)?;
+
+    let current = &schedule[current_index];
+    let next = &schedule[next_index];
+    let fade_start = next_at - next.fade_duration;
+    let progress = if instant < fade_start {
+        0.0
+    } else if next.fade_duration <= Duration::zero() {
+        1.0
+    } else {
+        let elapsed_ms = (instant - fade_start).num_milliseconds() as f32;
+        (elapsed_ms / next.fade_duration.num_milliseconds() as f32).clamp(0.0, 1.0)
+    };
+    let theme = if progress > 0.0 {
+        next.style.blend(&current.theme, &next.theme, progress)
+    } else {
+        current.theme.clone()
+    };
+
+    Some(ScheduleSample {
+        current_index,
+        current_at,
+        next_index,
+        next_at,
+        fade_start,
+        progress,
+        theme,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::theme::Color;
+    use futures::executor::block_on;
+    use gpui::Rgba;
+
+    fn theme(level: f32) -> InterpolatableTheme {
+        let color = Color::from_rgba(Rgba {
+            r: level,
+            g: level,
+            b: level,
+            a: 1.0,
+        });
+        InterpolatableTheme([("background".to_string(), color)].into_iter().collect())
+    }
+
+    fn entry(time: &str, zone: ScheduleZone, level: f32, fade_minutes: i64) -> ScheduleEntry {
+        ScheduleEntry {
+            time: ScheduleTime::parse(time, None).unwrap(),
+            zone,
+            theme: theme(level),
+            label: ThemeLabel::default(),
+            fade_duration: Duration::minutes(fade_minutes),
+            style: FadeStyle::default(),
+            days: ScheduleDays::every_day(),
+        }
+    }
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc()
+    }
+
+    /// Runs `schedule` as a one-shot interactive simulation, with the UI left
+    /// to animate fades, and returns the spans of the fades it started.
+    fn run_to_finish(schedule: Vec<ScheduleEntry>, clock: Arc<dyn Clock>) -> Vec<FadeSpan> {
+        let (sender, mut receiver) = event_channel();
+        let _handle = ThemeScheduler::spawn(
+            sender,
+            Arc::new(schedule),
+            AppMode::Interactive,
+            clock,
+            FadeDriver::Frames,
+            CatchUp::default(),
+        );
+        let mut spans = Vec::new();
+        let mut finished = false;
+        while let Some(event) = block_on(receiver.next()) {
+            match event {
+                SchedulerEvent::FadeStarted(span) => spans.push(span),
+                SchedulerEvent::Finished => finished = true,
+                _ => {}
+            }
+        }
+        assert!(finished, "the simulation never finished");
+        spans
+    }
+
+    #[test]
+    fn theme_at_passes_over_entries_that_have_not_occurred() {
+        let christmas = NaiveDate::from_ymd_opt(2024, 12, 25).unwrap();
+        let plan = Arc::new(DayPlan {
+            variants: vec![DayVariant {
+                days: DaySet::ALL,
+                dates: vec![DateRange {
+                    start: christmas,
+                    end: christmas,
+                }],
+            }],
+        });
+        let zone = ScheduleZone::Named(Tz::UTC);
+        let mut holiday = entry("08:00", zone, 1.0, 0);
+        holiday.days = ScheduleDays {
+            plan: plan.clone(),
+            variant: Some(0),
+        };
+        let mut daily = entry("12:00", zone, 0.5, 0);
+        daily.days = ScheduleDays {
+            plan,
+            variant: None,
+        };
+
+        let sample = theme_at(&[holiday, daily], utc("2024-03-01 15:00:00")).unwrap();
+
+        assert_eq!(sample.current_index, 1);
+        assert_eq!(sample.current_at, utc("2024-03-01 12:00:00"));
+        assert_eq!(sample.next_index, 1);
+        assert_eq!(sample.next_at, utc("2024-03-02 12:00:00"));
+    }
+
+    #[test]
+    fn fade_straddling_midnight_keeps_its_real_dates() {
+        let zone = ScheduleZone::Named(Tz::UTC);
+        let schedule = vec![entry("20:00", zone, 0.0, 0), entry("00:05", zone, 1.0, 10)];
+        let clock = Arc::new(ManualClock::new(utc("2024-03-01 23:00:00")));
+
+        let spans = run_to_finish(schedule, clock.clone());
+
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].start, utc("2024-03-01 23:55:00"));
+        assert_eq!(spans[0].end, utc("2024-03-02 00:05:00"));
+        assert!(clock.now() >= spans[0].end);
+    }
+
+    #[test]
+    fn time_in_spring_forward_gap_resolves_past_the_jump() {
+        let new_york = Tz::America__New_York;
+        let in_gap =
+            NaiveDateTime::parse_from_str("2024-03-10 02:30:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        // 02:30 EST doesn't exist; it is read as 03:30 EDT.
+        assert_eq!(resolve_in(&new_york, in_gap), utc("2024-03-10 07:30:00"));
+
+        let zone = ScheduleZone::Named(new_york);
+        let schedule = vec![entry("01:00", zone, 0.0, 0), entry("02:30", zone, 1.0, 10)];
+        let clock = Arc::new(ManualClock::new(utc("2024-03-10 06:30:00")));
+
+        let spans = run_to_finish(schedule, clock);
+
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].start, utc("2024-03-10 07:20:00"));
+        assert_eq!(spans[0].end, utc("2024-03-10 07:30:00"));
+    }
+
+    /// A `ManualClock` whose sleeps each wait for the test to allow them, so
+    /// commands can be sent at a known point. Free-running once `permits` is
+    /// dropped.
+    struct GatedClock {
+        clock: ManualClock,
+        permits: Mutex<std_mpsc::Receiver<()>>,
+    }
+
+    impl Clock for GatedClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.clock.now()
+        }
+
+        fn sleep(&self, duration: StdDuration) {
+            let _ = self.permits.lock().unwrap().recv();
+            self.clock.sleep(duration);
+        }
+
+        fn real_duration(&self, _duration: StdDuration) -> Option<StdDuration> {
+            None
+        }
+    }
+
+    #[test]
+    fn pausing_before_a_fade_shifts_it_by_the_paused_time() {
+        let zone = ScheduleZone::Named(Tz::UTC);
+        let schedule = vec![
+            entry("12:00:00", zone, 0.0, 0),
+            ScheduleEntry {
+                fade_duration: Duration::seconds(10),
+                ..entry("12:00:20", zone, 1.0, 0)
+            },
+        ];
+        let (permit, permits) = std_mpsc::channel();
+        let clock = Arc::new(GatedClock {
+            clock: ManualClock::new(utc("2024-06-01 12:00:00")),
+            permits: Mutex::new(permits),
+        });
+        let (sender, mut receiver) = event_channel();
+        let handle = ThemeScheduler::spawn(
+            sender,
+            Arc::new(schedule),
+            AppMode::Interactive,
+            clock.clone(),
+            FadeDriver::Frames,
+            CatchUp::default(),
+        );
+        let mut next_state = || loop {
+            if let SchedulerEvent::StateChange(state) = block_on(receiver.next()).unwrap() {
+                return state;
+            }
+        };
+
+        // Pause during the first wait before the fade, and stay paused a minute.
+        assert!(matches!(next_state(), SimulationState::Sleeping { .. }));
+        handle.pause();
+        permit.send(()).unwrap();
+        assert_eq!(next_state(), SimulationState::Paused);
+        clock.clock.advance(Duration::minutes(1));
+        handle.resume();
+        drop(permit);
+
+        let mut spans = Vec::new();
+        while let Some(event) = block_on(receiver.next()) {
+            if let SchedulerEvent::FadeStarted(span) = event {
+                spans.push(span);
+            }
+        }
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].start, utc("2024-06-01 12:01:10"));
+        assert_eq!(spans[0].end, utc("2024-06-01 12:01:20"));
+    }
+
+    #[test]
+    fn single_entry_is_applied_once_and_held() {
+        let zone = ScheduleZone::Named(Tz::UTC);
+        let (permit, permits) = std_mpsc::channel();
+        let clock = Arc::new(GatedClock {
+            clock: ManualClock::new(utc("2024-06-01 12:00:00")),
+            permits: Mutex::new(permits),
+        });
+        let (sender, mut receiver) = event_channel();
+        let handle = ThemeScheduler::spawn(
+            sender,
+            Arc::new(vec![entry("00:00", zone, 0.0, 5)]),
+            AppMode::Scheduler,
+            clock.clone(),
+            FadeDriver::Frames,
+            CatchUp::default(),
+        );
+
+        // Let three midnights pass, then stop the scheduler.
+        let held_until = utc("2024-06-04 12:00:00");
+        let whole_waits = (held_until - clock.now()).num_seconds() / MAX_IDLE_WAIT.as_secs() as i64;
+        for _ in 0..whole_waits {
+            permit.send(()).unwrap();
+        }
+        while clock.now() < held_until {
+            std::thread::yield_now();
+        }
+        drop(handle);
+        drop(permit);
+
+        let mut transitions = Vec::new();
+        while let Some(event) = block_on(receiver.next()) {
+            match event {
+                SchedulerEvent::Transition(transition) => transitions.push(transition.kind),
+                SchedulerEvent::FadeStarted(_) => panic!("a single entry never fades"),
+                _ => {}
+            }
+        }
+        assert_eq!(transitions, [TransitionKind::ThemeApplied]);
+    }
+}
@@ -1,58 +1,145 @@
 use crate::{
-    scheduler::{SchedulerEvent, ThemeScheduler},
+    appearance::Appearance,
+    clock::{clock_for_speed, Clock},
+    hooks::run_hooks,
+    scheduler::{
+        event_channel, EventReceiver, FadeSpan, ScheduleDays, ScheduleEntry, ScheduleZone,
+        SchedulerEvent, SchedulerHandle, ThemeScheduler,
+    },
     state::SimulationState,
-    theme::InterpolatableTheme,
-    AppState, AppView,
+    theme::{InterpolatableTheme, Theme},
+    AppMode, AppState, AppView,
 };
 use chrono::{Duration as ChronoDuration, Local};
 use futures::{channel::mpsc, StreamExt};
-use gpui::{AsyncApp, Context, SharedString, WeakEntity, prelude::*};
+use gpui::{AsyncApp, Context, WeakEntity, prelude::*};
 use std::sync::Arc;
 use tracing::info;
 
+/// A fade the view animates itself, one step per rendered frame
+/// (`FadeDriver::Frames`).
+pub struct FrameFade {
+    pub span: FadeSpan,
+    pub clock: Arc<dyn Clock>,
+}
+
+impl FrameFade {
+    /// The fade's progress and theme for the current instant; once finished,
+    /// the end theme itself.
+    pub fn sample(&self) -> (f32, InterpolatableTheme) {
+        let progress = self.span.progress_at(self.clock.now());
+        if progress >= 1.0 {
+            return (progress, self.span.to.clone());
+        }
+        (progress, self.span.theme_at(progress))
+    }
+}
+
 pub fn run_simulation_core(
     cx: &mut Context<AppView>,
-    start_theme: InterpolatableTheme,
-    end_theme: InterpolatableTheme,
+    start_theme: Theme,
+    end_theme: Theme,
     sleep_duration: ChronoDuration,
     fade_duration: ChronoDuration,
-    start_theme_name: SharedString,
-    end_theme_name: SharedString,
-) {
+) -> SchedulerHandle {
     info!(
         "Running simulation: Start='{}', End='{}'",
-        start_theme_name, end_theme_name
+        start_theme.name, end_theme.name
     );
 
+    let app_state = cx.global::<AppState>();
+    let (style, speed, fade_driver, catch_up) = (
+        app_state.simulation_fade_style(),
+        app_state.speed,
+        app_state.fade_driver,
+        app_state.catch_up(),
+    );
+
+    let (event_sender, event_receiver) = event_channel();
+    let clock = clock_for_speed(speed);
+    let now = clock.now().with_timezone(&Local).time();
+    let sim_schedule = Arc::new(vec![
+        ScheduleEntry {
+            time: now.into(),
+            zone: ScheduleZone::SystemLocal,
+            theme: start_theme.interpolatable_theme.clone(),
+            label: start_theme.label(),
+            fade_duration: ChronoDuration::seconds(0),
+            style,
+            days: ScheduleDays::every_day(),
+        },
+        ScheduleEntry {
+            time: (now + sleep_duration + fade_duration).into(),
+            zone: ScheduleZone::SystemLocal,
+            theme: end_theme.interpolatable_theme.clone(),
+            label: end_theme.label(),
+            fade_duration,
+            style,
+            days: ScheduleDays::every_day(),
+        },
+    ]);
+
+    // The sender is moved into the scheduler so the channel closes when its thread exits.
+    let handle = ThemeScheduler::spawn(
+        event_sender,
+        sim_schedule,
+        AppMode::Interactive,
+        clock.clone(),
+        fade_driver,
+        catch_up,
+    );
+    spawn_event_listener(cx, handle.id(), AppMode::Interactive, event_receiver, clock);
+    handle
+}
+
+/// Runs a real schedule (`AppMode::Scheduler`) until the returned handle is dropped.
+pub fn run_schedule_core(
+    cx: &mut Context<AppView>,
+    schedule: Arc<Vec<ScheduleEntry>>,
+) -> SchedulerHandle {
+    info!("Running schedule with {} entries", schedule.len());
+
+    let app_state = cx.global::<AppState>();
+    let (speed, fade_driver, catch_up) =
+        (app_state.speed, app_state.fade_driver, app_state.catch_up());
+
+    let (event_sender, event_receiver) = event_channel();
+    let clock = clock_for_speed(speed);
+    let handle = ThemeScheduler::spawn(
+        event_sender,
+        schedule,
+        AppMode::Scheduler,
+        clock.clone(),
+        fade_driver,
+        catch_up,
+    );
+    spawn_event_listener(cx, handle.id(), AppMode::Scheduler, event_receiver, clock);
+    handle
+}
+
+/// Applies a scheduler's events to the view until the run ends or is replaced.
+/// Only a real schedule (`AppMode::Scheduler`) runs hooks and writes Zed's
+/// settings; previews in the simulator don't.
+fn spawn_event_listener(
+    cx: &mut Context<AppView>,
+    handle_id: usize,
+    app_mode: AppMode,
+    mut event_receiver: EventReceiver,
+    clock: Arc<dyn Clock>,
+) {
     cx.spawn(move |view: WeakEntity<AppView>, cx: &mut AsyncApp| {
         let mut cx = cx.clone();
         async move {
-            let (event_sender, mut event_receiver) = mpsc::channel(32);
-            let now = Local::now().time();
-            let sim_schedule = Arc::new(vec![
-            crate::scheduler::ScheduleEntry {
-                time: now,
-                theme: start_theme.clone(),
-                fade_duration: ChronoDuration::seconds(0),
-            },
-            crate::scheduler::ScheduleEntry {
-                time: now + sleep_duration + fade_duration,
-                theme: end_theme.clone(),
-                fade_duration,
-            },
-        ]);
-
-        ThemeScheduler::spawn(
-            event_sender.clone(),
-            sim_schedule,
-            crate::AppMode::Interactive,
-        );
-
         while let Some(event) = event_receiver.next().await {
             // We update the view on the main thread
-            let _ = view.update(&mut cx, |view, cx| {
+            let still_current = view.update(&mut cx, |view, cx| {
+                // Events still queued from a cancelled run must not touch the UI.
+                if view.scheduler_handle.as_ref().map(SchedulerHandle::id) != Some(handle_id) {
+                    return false;
+                }
                 match event {
                     SchedulerEvent::ThemeUpdate(theme) => {
+                        view.frame_fade = None;
                         cx.update_global::<AppState, _>(|app_state, _| {
                             app_state.active_theme = theme;
                         });
@@ -65,6 +152,29 @@ pub fn run_simulation_core(
                         view.simulation_state = state;
                         cx.notify();
                     }
+                    SchedulerEvent::FadeStarted(span) => {
+                        // `AppView::render` takes it from here, frame by frame.
+                        view.frame_fade = Some(FrameFade {
+                            span,
+                            clock: clock.clone(),
+                        });
+                        view.simulation_state = SimulationState::Fading { progress: 0.0 };
+                        cx.notify();
+                    }
+                    SchedulerEvent::Transition(transition) => {
+                        let app_state = cx.global::<AppState>();
+                        if app_mode == AppMode::Scheduler {
+                            if let Some(schedule) = &app_state.schedule {
+                                run_hooks(&schedule.hooks, &transition);
+                            }
+                            if let Some(writer) = &app_state.zed_settings {
+                                writer.on_event(
+                                    &SchedulerEvent::Transition(transition),
+                                    app_state.desktop_appearance(),
+                                );
+                            }
+                        }
+                    }
                     SchedulerEvent::Finished => {
                         info!("Simulation Finished Event Received");
                         
@@ -76,15 +186,74 @@ pub fn run_simulation_core(
                             state.start_theme_index = old_end;
                             state.end_theme_index = old_start;
                         });
+                        view.refresh_end_recommendations(cx);
 
+                        view.scheduler_handle = None;
+                        view.frame_fade = None;
                         view.simulation_state = SimulationState::Idle;
                         cx.notify();
                     }
                 }
+                true
             });
+            // Stop listening once the view is gone or the run was replaced. Dropping
+            // the receiver makes the scheduler thread cancel itself.
+            if !matches!(still_current, Ok(true)) {
+                break;
+            }
         }
         info!("Simulation channel closed.");
         }
     })
     .detach();
 }
+
+/// Passes appearance changes from the watcher threads to the view.
+pub fn spawn_appearance_listener(
+    cx: &mut Context<AppView>,
+    mut appearances: mpsc::UnboundedReceiver<Appearance>,
+) {
+    cx.spawn(move |view: WeakEntity<AppView>, cx: &mut AsyncApp| {
+        let mut cx = cx.clone();
+        async move {
+            while let Some(appearance) = appearances.next().await {
+                if view
+                    .update(&mut cx, |view, cx| view.set_appearance(appearance, cx))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }
+    })
+    .detach();
+}
+
+/// How often, in schedule time, the dashboard re-renders between scheduler
+/// events so the timeline's "now" line keeps moving.
+const DASHBOARD_TICK: std::time::Duration = std::time::Duration::from_secs(30);
+/// The shortest real interval between those re-renders, however fast `--speed` is.
+const MIN_DASHBOARD_TICK: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Re-renders the view periodically while it shows the scheduler dashboard.
+pub fn spawn_dashboard_ticker(cx: &mut Context<AppView>) {
+    let speed = cx.global::<AppState>().speed;
+    let interval = DASHBOARD_TICK.div_f64(speed).max(MIN_DASHBOARD_TICK);
+    cx.spawn(move |view: WeakEntity<AppView>, cx: &mut AsyncApp| {
+        let mut cx = cx.clone();
+        async move {
+            loop {
+                cx.background_executor().timer(interval).await;
+                let updated = view.update(&mut cx, |_, cx| {
+                    if cx.global::<AppState>().app_mode == crate::AppMode::Scheduler {
+                        cx.notify();
+                    }
+                });
+                if updated.is_err() {
+                    break;
+                }
+            }
+        }
+    })
+    .detach();
+}
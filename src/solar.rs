@@ -0,0 +1,117 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use std::fmt;
+
+// --- SOLAR EVENT CALCULATION ---
+//
+// Implements the NOAA-style "sunrise equation". It is accurate to within a
+// minute or two for latitudes below the polar circles, which is plenty for
+// deciding when to fade a theme, and needs no network access.
+
+/// A point on Earth. Latitude is positive north, longitude positive east.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeoLocation {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolarEvent {
+    NauticalDawn,
+    CivilDawn,
+    Sunrise,
+    Sunset,
+    CivilDusk,
+    NauticalDusk,
+}
+
+impl SolarEvent {
+    /// The sun's zenith angle (in degrees) at which the event happens.
+    /// Sunrise and sunset include atmospheric refraction and the sun's radius.
+    fn zenith_degrees(&self) -> f64 {
+        match self {
+            Self::Sunrise | Self::Sunset => 90.833,
+            Self::CivilDawn | Self::CivilDusk => 96.0,
+            Self::NauticalDawn | Self::NauticalDusk => 102.0,
+        }
+    }
+
+    fn is_morning(&self) -> bool {
+        matches!(self, Self::NauticalDawn | Self::CivilDawn | Self::Sunrise)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::NauticalDawn => "nautical_dawn",
+            Self::CivilDawn => "civil_dawn",
+            Self::Sunrise => "sunrise",
+            Self::Sunset => "sunset",
+            Self::CivilDusk => "civil_dusk",
+            Self::NauticalDusk => "nautical_dusk",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "nautical_dawn" => Some(Self::NauticalDawn),
+            "civil_dawn" => Some(Self::CivilDawn),
+            "sunrise" => Some(Self::Sunrise),
+            "sunset" => Some(Self::Sunset),
+            "civil_dusk" => Some(Self::CivilDusk),
+            "nautical_dusk" => Some(Self::NauticalDusk),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SolarEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Julian date of 1970-01-01T00:00:00Z.
+const UNIX_EPOCH_JULIAN: f64 = 2440587.5;
+/// Julian date of the J2000.0 epoch (2000-01-01T12:00:00 TT).
+const J2000: f64 = 2451545.0;
+const EARTH_AXIAL_TILT: f64 = 23.4397;
+
+/// When `event` happens on `date` at `location`, or `None` if the sun never
+/// reaches that elevation that day (polar day or night).
+pub fn solar_event_time(
+    event: SolarEvent,
+    date: NaiveDate,
+    location: GeoLocation,
+) -> Option<DateTime<Utc>> {
+    let days_since_unix_epoch = (date - NaiveDate::from_ymd_opt(1970, 1, 1)?).num_days() as f64;
+    let julian_noon = UNIX_EPOCH_JULIAN + days_since_unix_epoch + 0.5;
+    let day_number = (julian_noon - J2000 + 0.0008).round();
+
+    // Mean solar time, solar mean anomaly and equation of the center.
+    let mean_solar_time = day_number - location.longitude / 360.0;
+    let mean_anomaly = (357.5291 + 0.98560028 * mean_solar_time).rem_euclid(360.0);
+    let m = mean_anomaly.to_radians();
+    let center = 1.9148 * m.sin() + 0.0200 * (2.0 * m).sin() + 0.0003 * (3.0 * m).sin();
+
+    let ecliptic_longitude = (mean_anomaly + center + 180.0 + 102.9372).rem_euclid(360.0);
+    let lambda = ecliptic_longitude.to_radians();
+    let solar_transit = J2000 + mean_solar_time + 0.0053 * m.sin() - 0.0069 * (2.0 * lambda).sin();
+
+    let declination_sin = lambda.sin() * EARTH_AXIAL_TILT.to_radians().sin();
+    let declination_cos = declination_sin.asin().cos();
+    let phi = location.latitude.to_radians();
+
+    let hour_angle_cos = (event.zenith_degrees().to_radians().cos() - phi.sin() * declination_sin)
+        / (phi.cos() * declination_cos);
+    if !(-1.0..=1.0).contains(&hour_angle_cos) {
+        return None;
+    }
+    let hour_angle = hour_angle_cos.acos().to_degrees();
+
+    let julian_event = if event.is_morning() {
+        solar_transit - hour_angle / 360.0
+    } else {
+        solar_transit + hour_angle / 360.0
+    };
+    let unix_millis = ((julian_event - UNIX_EPOCH_JULIAN) * 86_400_000.0).round() as i64;
+    DateTime::from_timestamp_millis(unix_millis)
+}
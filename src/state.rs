@@ -3,14 +3,53 @@ pub enum SimulationState {
     Idle,
     Sleeping { seconds_remaining: usize },
     Fading { progress: f32 },
+    Paused,
+    /// Showing a theme picked by hand; the schedule resumes in `seconds_remaining`.
+    Overridden { seconds_remaining: usize },
 }
 
 impl SimulationState {
     pub fn display(&self) -> String {
         match self {
             Self::Idle => String::new(),
-            Self::Sleeping { seconds_remaining } => format!("Status: Sleeping... {}s", seconds_remaining),
+            Self::Sleeping { seconds_remaining } => {
+                format!(
+                    "Status: Sleeping... {}",
+                    format_countdown(*seconds_remaining)
+                )
+            }
             Self::Fading { .. } => "Status: Fading...".to_string(),
+            Self::Paused => "Status: Paused".to_string(),
+            Self::Overridden { seconds_remaining } => {
+                format!(
+                    "Status: Manual theme, schedule resumes in {}",
+                    format_countdown(*seconds_remaining)
+                )
+            }
         }
     }
 }
+
+/// Formats a countdown as seconds under two minutes, minutes under two hours,
+/// and hours beyond that. The scheduler wakes whenever this text would change.
+pub fn format_countdown(seconds: usize) -> String {
+    if seconds <= 120 {
+        format!("{}s", seconds)
+    } else if seconds <= 2 * 60 * 60 {
+        format!("{}m", seconds.div_ceil(60))
+    } else {
+        format!("{}h", seconds.div_ceil(60 * 60))
+    }
+}
+
+/// Formats a duration for display, e.g. "45s", "30m" or "1h30m".
+pub fn format_duration(duration: chrono::Duration) -> String {
+    let minutes = duration.num_minutes();
+    if minutes >= 60 {
+        format!("{}h{:02}m", minutes / 60, minutes % 60)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", duration.num_seconds())
+    }
+}
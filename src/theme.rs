@@ -6,6 +6,8 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use std::{collections::HashMap, str::FromStr};
 
+use crate::appearance::Appearance;
+
 // --- THEME & COLOR DEFINITIONS (from scheduler.rs) ---
 
 #[derive(Clone, Copy, Debug)]
@@ -64,32 +66,287 @@ pub fn lerp_color(a: Color, b: Color, t: f32) -> Color {
     Color { rgba, hsla }
 }
 
+impl Color {
+    pub fn from_rgba(rgba: Rgba) -> Self {
+        Color {
+            rgba,
+            hsla: Hsla::from(rgba),
+        }
+    }
+
+    pub fn is_translucent(&self) -> bool {
+        self.rgba.a < 1.0
+    }
+
+    /// Returns the opaque color you actually see when `self` is drawn over `backdrop`.
+    pub fn composite_over(&self, backdrop: Color) -> Color {
+        let a = self.rgba.a;
+        Color::from_rgba(Rgba {
+            r: self.rgba.r * a + backdrop.rgba.r * (1.0 - a),
+            g: self.rgba.g * a + backdrop.rgba.g * (1.0 - a),
+            b: self.rgba.b * a + backdrop.rgba.b * (1.0 - a),
+            a: 1.0,
+        })
+    }
+}
+
+/// How translucent colors are treated while interpolating.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlphaMode {
+    /// Interpolate each HSLA channel independently (the original behavior).
+    #[default]
+    Straight,
+    /// Interpolate in RGB with color channels premultiplied by alpha.
+    Premultiplied,
+    /// Composite translucent colors over their backdrop key first, interpolate
+    /// the visible result, then recover a translucent color at the interpolated alpha.
+    Composite,
+}
+
+impl AlphaMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Straight => "Straight",
+            Self::Premultiplied => "Premultiplied",
+            Self::Composite => "Composite",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Straight => Self::Premultiplied,
+            Self::Premultiplied => Self::Composite,
+            Self::Composite => Self::Straight,
+        }
+    }
+}
+
+pub fn lerp_color_premultiplied(a: Color, b: Color, t: f32) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    // Unpremultiplying doesn't round-trip exactly, so the endpoints are returned as given.
+    if t == 0.0 {
+        return a;
+    } else if t == 1.0 {
+        return b;
+    }
+    let alpha = a.rgba.a + (b.rgba.a - a.rgba.a) * t;
+    let channel = |ca: f32, cb: f32| {
+        let premultiplied = ca * a.rgba.a + (cb * b.rgba.a - ca * a.rgba.a) * t;
+        if alpha > 0.0 {
+            (premultiplied / alpha).clamp(0.0, 1.0)
+        } else {
+            ca + (cb - ca) * t
+        }
+    };
+    Color::from_rgba(Rgba {
+        r: channel(a.rgba.r, b.rgba.r),
+        g: channel(a.rgba.g, b.rgba.g),
+        b: channel(a.rgba.b, b.rgba.b),
+        a: alpha,
+    })
+}
+
+/// Interpolates the *visible* color of two translucent colors drawn over their
+/// respective backdrops, then solves for the translucent color that produces
+/// that visible color over the interpolated backdrop.
+pub fn lerp_color_composited(
+    a: Color,
+    b: Color,
+    backdrop_a: Color,
+    backdrop_b: Color,
+    t: f32,
+) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    if t == 0.0 {
+        return a;
+    } else if t == 1.0 {
+        return b;
+    }
+    let visible_a = a.composite_over(backdrop_a);
+    let visible_b = b.composite_over(backdrop_b);
+    let backdrop = lerp_color_premultiplied(backdrop_a, backdrop_b, t);
+    let visible = lerp_color_premultiplied(visible_a, visible_b, t);
+    let alpha = a.rgba.a + (b.rgba.a - a.rgba.a) * t;
+    if alpha <= 0.0 {
+        return Color::from_rgba(Rgba {
+            a: 0.0,
+            ..visible.rgba
+        });
+    }
+    let channel = |v: f32, bg: f32| ((v - bg * (1.0 - alpha)) / alpha).clamp(0.0, 1.0);
+    Color::from_rgba(Rgba {
+        r: channel(visible.rgba.r, backdrop.rgba.r),
+        g: channel(visible.rgba.g, backdrop.rgba.g),
+        b: channel(visible.rgba.b, backdrop.rgba.b),
+        a: alpha,
+    })
+}
+
+/// The space opaque colors are interpolated in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorSpace {
+    /// Interpolate hue, saturation and lightness (the original behavior).
+    #[default]
+    Hsl,
+    /// Interpolate sRGB channels. Never sweeps through unrelated hues, but
+    /// midpoints between saturated colors can look muddy.
+    Rgb,
+    /// Interpolate CIE L*a*b*, so lightness changes evenly to the eye.
+    Lab,
+}
+
+impl ColorSpace {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Hsl => "HSL",
+            Self::Rgb => "RGB",
+            Self::Lab => "Lab",
+        }
+    }
+
+    pub fn lerp(&self, a: Color, b: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let alpha = a.rgba.a + (b.rgba.a - a.rgba.a) * t;
+        match self {
+            Self::Hsl => lerp_color(a, b, t),
+            Self::Rgb => Color::from_rgba(Rgba {
+                r: a.rgba.r + (b.rgba.r - a.rgba.r) * t,
+                g: a.rgba.g + (b.rgba.g - a.rgba.g) * t,
+                b: a.rgba.b + (b.rgba.b - a.rgba.b) * t,
+                a: alpha,
+            }),
+            Self::Lab => {
+                let (lab_a, lab_b) = (Lab::from(a.rgba), Lab::from(b.rgba));
+                let lab = Lab {
+                    l: lab_a.l + (lab_b.l - lab_a.l) * t,
+                    a: lab_a.a + (lab_b.a - lab_a.a) * t,
+                    b: lab_a.b + (lab_b.b - lab_a.b) * t,
+                };
+                Color::from_rgba(lab.to_rgba(alpha))
+            }
+        }
+    }
+}
+
+/// The key whose color a translucent key is normally drawn on top of.
+pub fn backdrop_key(key: &str) -> &'static str {
+    if key.starts_with("editor.")
+        || key.starts_with("players.")
+        || key.starts_with("search.")
+        || key.starts_with("terminal.")
+    {
+        "editor.background"
+    } else if key.starts_with("panel.") {
+        "panel.background"
+    } else if key.starts_with("elevated_surface.") {
+        "elevated_surface.background"
+    } else {
+        "background"
+    }
+}
+
 #[derive(Clone, Default, Debug)]
 pub struct InterpolatableTheme(pub HashMap<String, Color>);
 
+impl InterpolatableTheme {
+    /// The opaque backdrop for `key`, falling back to `background` when the
+    /// specific backdrop key is missing or itself translucent.
+    pub fn backdrop_for(&self, key: &str) -> Color {
+        let fallback = self.0.get("background").copied().unwrap_or_default();
+        match self.0.get(backdrop_key(key)) {
+            Some(color) if !color.is_translucent() => *color,
+            Some(color) => color.composite_over(fallback),
+            None => fallback,
+        }
+    }
+}
+
 pub fn lerp_theme(a: &InterpolatableTheme, b: &InterpolatableTheme, t: f32) -> InterpolatableTheme {
+    lerp_theme_with(a, b, t, AlphaMode::Straight)
+}
+
+pub fn lerp_theme_with(
+    a: &InterpolatableTheme,
+    b: &InterpolatableTheme,
+    t: f32,
+    alpha_mode: AlphaMode,
+) -> InterpolatableTheme {
+    lerp_theme_in(a, b, t, alpha_mode, ColorSpace::Hsl)
+}
+
+/// Like `lerp_theme_with`, interpolating opaque colors in `color_space`.
+/// Translucent colors under the premultiplied and composite alpha modes are
+/// always blended in RGB.
+pub fn lerp_theme_in(
+    a: &InterpolatableTheme,
+    b: &InterpolatableTheme,
+    t: f32,
+    alpha_mode: AlphaMode,
+    color_space: ColorSpace,
+) -> InterpolatableTheme {
     let mut new_theme = InterpolatableTheme::default();
 
     for (key, color_a) in &a.0 {
-        if let Some(color_b) = b.0.get(key) {
-            new_theme
-                .0
-                .insert(key.clone(), lerp_color(*color_a, *color_b, t));
-        } else {
-            new_theme.0.insert(key.clone(), *color_a);
-        }
+        let color = lerp_key_in(a, b, key, t, alpha_mode, color_space).unwrap_or(*color_a);
+        new_theme.0.insert(key.clone(), color);
     }
     new_theme
 }
 
+/// The color of a single `key` in `lerp_theme_in`, or `None` if either theme
+/// lacks it.
+pub fn lerp_key_in(
+    a: &InterpolatableTheme,
+    b: &InterpolatableTheme,
+    key: &str,
+    t: f32,
+    alpha_mode: AlphaMode,
+    color_space: ColorSpace,
+) -> Option<Color> {
+    let (color_a, color_b) = (*a.0.get(key)?, *b.0.get(key)?);
+    let translucent = color_a.is_translucent() || color_b.is_translucent();
+    Some(match alpha_mode {
+        AlphaMode::Premultiplied if translucent => lerp_color_premultiplied(color_a, color_b, t),
+        AlphaMode::Composite if translucent => lerp_color_composited(
+            color_a,
+            color_b,
+            a.backdrop_for(key),
+            b.backdrop_for(key),
+            t,
+        ),
+        _ => color_space.lerp(color_a, color_b, t),
+    })
+}
+
 // --- THEME PARSING (from main.rs) ---
 
 #[derive(Clone, Debug)]
 pub struct Theme {
     pub name: String,
+    /// Whether the theme file marks it as a light or a dark theme.
+    pub appearance: Option<Appearance>,
     pub interpolatable_theme: InterpolatableTheme,
 }
 
+impl Theme {
+    pub fn label(&self) -> ThemeLabel {
+        ThemeLabel {
+            name: self.name.clone(),
+            appearance: self.appearance,
+        }
+    }
+}
+
+/// Which theme some colors are, for whatever needs more than the colors, such
+/// as hooks or Zed's settings.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ThemeLabel {
+    pub name: String,
+    pub appearance: Option<Appearance>,
+}
+
 #[derive(Deserialize, Debug, JsonSchema)]
 pub struct ZedThemeFile {
     pub themes: Vec<ThemeDefinition>,
@@ -98,6 +355,9 @@ pub struct ZedThemeFile {
 #[derive(Deserialize, Debug, JsonSchema)]
 pub struct ThemeDefinition {
     pub name: String,
+    /// "light" or "dark".
+    #[serde(default)]
+    pub appearance: Option<String>,
     pub style: ThemeStyle,
 }
 
@@ -139,6 +399,306 @@ pub fn flatten_colors(
             let nested_map: HashMap<String, serde_json::Value> =
                 nested_obj.clone().into_iter().collect();
             flatten_colors(&nested_map, interpolatable_theme, &new_key);
+        } else if let Some(players) = value.as_array().filter(|_| new_key == "players") {
+            // Each player's colors are keyed by its index, e.g. `players.0.selection`.
+            // Other arrays hold no colors that pair up between themes.
+            for (index, player) in players.iter().enumerate() {
+                if let Some(player) = player.as_object() {
+                    let player_map: HashMap<String, serde_json::Value> =
+                        player.clone().into_iter().collect();
+                    let player_key = format!("players.{}", index);
+                    flatten_colors(&player_map, interpolatable_theme, &player_key);
+                }
+            }
+        }
+    }
+}
+
+// --- PERCEPTUAL COLOR METRICS ---
+
+/// A color in CIE L*a*b* space (D65 white point), used for perceptual comparisons.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+impl From<Rgba> for Lab {
+    fn from(rgba: Rgba) -> Self {
+        fn linearize(c: f32) -> f32 {
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        }
+        fn f(t: f32) -> f32 {
+            if t > 216.0 / 24389.0 {
+                t.cbrt()
+            } else {
+                (24389.0 / 27.0 * t + 16.0) / 116.0
+            }
+        }
+
+        let (r, g, b) = (linearize(rgba.r), linearize(rgba.g), linearize(rgba.b));
+        let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+
+        Lab {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
         }
     }
 }
+
+impl Lab {
+    /// Converts back to sRGB, clamping colors outside the sRGB gamut.
+    pub fn to_rgba(self, alpha: f32) -> Rgba {
+        fn f_inverse(t: f32) -> f32 {
+            let cubed = t * t * t;
+            if cubed > 216.0 / 24389.0 {
+                cubed
+            } else {
+                (116.0 * t - 16.0) / (24389.0 / 27.0)
+            }
+        }
+        fn gamma(c: f32) -> f32 {
+            let c = if c <= 0.0031308 {
+                12.92 * c
+            } else {
+                1.055 * c.powf(1.0 / 2.4) - 0.055
+            };
+            c.clamp(0.0, 1.0)
+        }
+
+        let fy = (self.l + 16.0) / 116.0;
+        let fx = fy + self.a / 500.0;
+        let fz = fy - self.b / 200.0;
+        let x = f_inverse(fx) * 0.95047;
+        let y = f_inverse(fy);
+        let z = f_inverse(fz) * 1.08883;
+
+        Rgba {
+            r: gamma(3.2406 * x - 1.5372 * y - 0.4986 * z),
+            g: gamma(-0.9689 * x + 1.8758 * y + 0.0415 * z),
+            b: gamma(0.0557 * x - 0.2040 * y + 1.0570 * z),
+            a: alpha,
+        }
+    }
+}
+
+/// CIEDE2000 color difference. Roughly: < 1 is imperceptible, 2-10 is noticeable
+/// at a glance, and > 50 means the colors are nearly opposite.
+pub fn delta_e(lab1: Lab, lab2: Lab) -> f32 {
+    let (l1, a1, b1) = (lab1.l as f64, lab1.a as f64, lab1.b as f64);
+    let (l2, a2, b2) = (lab2.l as f64, lab2.a as f64, lab2.b as f64);
+    let pow25_7 = 25f64.powi(7);
+
+    let c_bar = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let g = 0.5 * (1.0 - (c_bar.powi(7) / (c_bar.powi(7) + pow25_7)).sqrt());
+    let a1p = a1 * (1.0 + g);
+    let a2p = a2 * (1.0 + g);
+    let c1p = a1p.hypot(b1);
+    let c2p = a2p.hypot(b2);
+
+    let hue = |b: f64, a: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let h1p = hue(b1, a1p);
+    let h2p = hue(b2, a2p);
+
+    let delta_lp = l2 - l1;
+    let delta_cp = c2p - c1p;
+    let delta_hp = if c1p * c2p == 0.0 {
+        0.0
+    } else {
+        let d = h2p - h1p;
+        if d > 180.0 {
+            d - 360.0
+        } else if d < -180.0 {
+            d + 360.0
+        } else {
+            d
+        }
+    };
+    let delta_big_hp = 2.0 * (c1p * c2p).sqrt() * (delta_hp.to_radians() / 2.0).sin();
+
+    let l_bar_p = (l1 + l2) / 2.0;
+    let c_bar_p = (c1p + c2p) / 2.0;
+    let h_bar_p = if c1p * c2p == 0.0 {
+        h1p + h2p
+    } else if (h1p - h2p).abs() <= 180.0 {
+        (h1p + h2p) / 2.0
+    } else if h1p + h2p < 360.0 {
+        (h1p + h2p + 360.0) / 2.0
+    } else {
+        (h1p + h2p - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (h_bar_p - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_bar_p).to_radians().cos()
+        + 0.32 * (3.0 * h_bar_p + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_bar_p - 63.0).to_radians().cos();
+    let delta_theta = 30.0 * (-((h_bar_p - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (c_bar_p.powi(7) / (c_bar_p.powi(7) + pow25_7)).sqrt();
+    let s_l = 1.0 + 0.015 * (l_bar_p - 50.0).powi(2) / (20.0 + (l_bar_p - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_bar_p;
+    let s_h = 1.0 + 0.015 * c_bar_p * t;
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    let (dl, dc, dh) = (delta_lp / s_l, delta_cp / s_c, delta_big_hp / s_h);
+    (dl * dl + dc * dc + dh * dh + r_t * dc * dh).sqrt() as f32
+}
+
+/// The hue angle (in degrees) that `lerp_color` sweeps through going from `a` to `b`.
+///
+/// `lerp_color` interpolates hue linearly without wrapping, so a red-to-magenta
+/// fade travels the long way round the color wheel. Near-gray colors have no
+/// meaningful hue and report no sweep.
+pub fn hue_sweep_degrees(a: Color, b: Color) -> f32 {
+    const MIN_SATURATION: f32 = 0.1;
+    if a.hsla.s < MIN_SATURATION || b.hsla.s < MIN_SATURATION {
+        return 0.0;
+    }
+    (b.hsla.h - a.hsla.h).abs() * 360.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn color(hex: &str) -> Color {
+        Color::from_str(hex).unwrap()
+    }
+
+    fn theme(colors: &[(&str, &str)]) -> InterpolatableTheme {
+        InterpolatableTheme(
+            colors
+                .iter()
+                .map(|(key, hex)| (key.to_string(), color(hex)))
+                .collect(),
+        )
+    }
+
+    fn assert_close(actual: Rgba, expected: Rgba) {
+        let channels = [
+            (actual.r, expected.r),
+            (actual.g, expected.g),
+            (actual.b, expected.b),
+            (actual.a, expected.a),
+        ];
+        assert!(
+            channels.iter().all(|(a, e)| (a - e).abs() < 0.5 / 255.0),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn endpoints_come_back_exactly() {
+        let (a, b) = (color("#83899480"), color("#e5c07bff"));
+        let (backdrop_a, backdrop_b) = (color("#282c34"), color("#fafafa"));
+        assert_eq!(lerp_color_premultiplied(a, b, 0.0).rgba, a.rgba);
+        assert_eq!(lerp_color_premultiplied(a, b, 1.0).rgba, b.rgba);
+        assert_eq!(
+            lerp_color_composited(a, b, backdrop_a, backdrop_b, 0.0).rgba,
+            a.rgba
+        );
+        assert_eq!(
+            lerp_color_composited(a, b, backdrop_a, backdrop_b, 1.0).rgba,
+            b.rgba
+        );
+    }
+
+    #[test]
+    fn transparent_endpoint_takes_no_hue() {
+        let (clear_red, blue) = (color("#ff000000"), color("#0000ffff"));
+        let white = color("#ffffff");
+        for t in [0.25, 0.5, 0.75] {
+            let premultiplied = lerp_color_premultiplied(clear_red, blue, t);
+            assert_close(
+                premultiplied.rgba,
+                Rgba {
+                    r: 0.0,
+                    g: 0.0,
+                    b: 1.0,
+                    a: t,
+                },
+            );
+            let composited = lerp_color_composited(clear_red, blue, white, white, t);
+            assert_close(
+                composited.rgba,
+                Rgba {
+                    r: 0.0,
+                    g: 0.0,
+                    b: 1.0,
+                    a: t,
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn composites_over_the_backdrop_key() {
+        let theme = theme(&[
+            ("background", "#21252b"),
+            ("editor.background", "#282c34"),
+            ("panel.background", "#ffffff80"),
+        ]);
+        assert_close(
+            color("#83899480")
+                .composite_over(theme.backdrop_for("editor.document_highlight.read_background"))
+                .rgba,
+            color("#565b64").rgba,
+        );
+        assert_close(
+            theme.backdrop_for("players.0.selection").rgba,
+            color("#282c34").rgba,
+        );
+        // A translucent backdrop is itself composited over `background`.
+        assert_close(
+            theme.backdrop_for("panel.focused_border").rgba,
+            color("#909295").rgba,
+        );
+        assert_close(theme.backdrop_for("border").rgba, color("#21252b").rgba);
+    }
+
+    #[test]
+    fn flattens_players_by_index() {
+        let colors: HashMap<String, serde_json::Value> = serde_json::from_str(
+            r##"{
+                "players": [
+                    { "cursor": "#74ade8ff", "selection": "#74ade83d" },
+                    { "cursor": "#be5046ff", "selection": "#be50463d" }
+                ],
+                "accents": ["#74ade8ff"],
+                "syntax": { "comment": { "color": "#5d636fff" } }
+            }"##,
+        )
+        .unwrap();
+        let mut theme = InterpolatableTheme::default();
+        flatten_colors(&colors, &mut theme, "");
+
+        let mut keys = theme.0.keys().map(String::as_str).collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(
+            keys,
+            [
+                "players.0.cursor",
+                "players.0.selection",
+                "players.1.cursor",
+                "players.1.selection",
+                "syntax.comment.color",
+            ]
+        );
+    }
+}
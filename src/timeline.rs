@@ -0,0 +1,97 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use gpui::Hsla;
+
+use crate::{
+    appearance::Appearance,
+    scheduler::{occurrences_between, theme_at, ScheduleEntry, ScheduleZone},
+};
+
+// --- DAY TIMELINE ---
+//
+// A local calendar day of a schedule, reduced to what the timeline component
+// draws: one color per slice of the day, where entries take effect, and where
+// fades run. Sampling blends whole themes, so it is done once per day and
+// cached rather than on every render.
+
+/// How many slices the day is sampled in (one every 15 minutes).
+pub const TIMELINE_SLICES: usize = 96;
+
+/// The theme keys the timeline can be colored by.
+pub const TIMELINE_KEYS: &[&str] = &[
+    "editor.background",
+    "surface.background",
+    "text",
+    "text.accent",
+    "border",
+];
+
+/// What a `DayTimeline` was built from; it is rebuilt when any of it changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimelineSource {
+    pub profile: usize,
+    pub appearance: Appearance,
+    pub day: NaiveDate,
+    pub key: &'static str,
+}
+
+pub struct DayTimeline {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// The key's color in the middle of each slice; `None` where the theme lacks it.
+    pub colors: Vec<Option<Hsla>>,
+    /// Where entries take effect, as fractions of the day.
+    pub markers: Vec<f32>,
+    /// Fade windows as `(start, end)` fractions of the day, clipped to it.
+    pub fades: Vec<(f32, f32)>,
+}
+
+impl DayTimeline {
+    /// Samples `schedule` over the local calendar day `day`, coloring it by `key`.
+    pub fn build(schedule: &[ScheduleEntry], day: NaiveDate, key: &str) -> Self {
+        let start = ScheduleZone::SystemLocal.resolve(day.and_time(NaiveTime::MIN));
+        let end =
+            ScheduleZone::SystemLocal.resolve((day + Duration::days(1)).and_time(NaiveTime::MIN));
+        let mut timeline = Self {
+            start,
+            end,
+            colors: Vec::with_capacity(TIMELINE_SLICES),
+            markers: Vec::new(),
+            fades: Vec::new(),
+        };
+
+        for slice in 0..TIMELINE_SLICES {
+            let color = theme_at(schedule, timeline.slice_instant(slice))
+                .and_then(|sample| sample.theme.0.get(key).map(|c| c.hsla));
+            timeline.colors.push(color);
+        }
+
+        // A fade that ends early tomorrow may already be running late today.
+        let occurrences = occurrences_between(schedule, start, end + Duration::days(1));
+        for (index, at) in occurrences {
+            let fade_start = at - schedule[index].fade_duration;
+            if at < end {
+                timeline.markers.push(timeline.fraction_of(at));
+            }
+            if fade_start < at && fade_start < end && at > start {
+                timeline
+                    .fades
+                    .push((timeline.fraction_of(fade_start), timeline.fraction_of(at)));
+            }
+        }
+        timeline
+    }
+
+    /// How far through the day `instant` is, clamped to 0.0..=1.0.
+    pub fn fraction_of(&self, instant: DateTime<Utc>) -> f32 {
+        let length = (self.end - self.start).num_milliseconds() as f32;
+        let elapsed = (instant - self.start).num_milliseconds() as f32;
+        (elapsed / length).clamp(0.0, 1.0)
+    }
+
+    /// The middle of slice `slice`, which is when its color is sampled.
+    pub fn slice_instant(&self, slice: usize) -> DateTime<Utc> {
+        let length = (self.end - self.start).num_milliseconds();
+        let offset = length * (2 * slice as i64 + 1) / (2 * TIMELINE_SLICES as i64);
+        self.start + Duration::milliseconds(offset)
+    }
+}
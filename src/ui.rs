@@ -3,9 +3,21 @@ use crate::components::dropdown::render_dropdown;
 use crate::components::form_field::render_form_field;
 use crate::components::gradient_bar::render_gradient_bar;
 use crate::components::panel::render_panel;
-use crate::state::SimulationState;
-use crate::AppView;
-use gpui::{div, prelude::*, rems, Context, IntoElement};
+use crate::components::profile_selector::render_profile_selector;
+use crate::components::progress_bar::render_progress_bar;
+use crate::components::schedule_diagnostics::render_schedule_diagnostics;
+use crate::components::schedule_list::{render_schedule_list, ScheduleRow};
+use crate::components::theme_diff::render_theme_diff;
+use crate::components::timeline::render_timeline;
+use crate::diff::ThemeDiff;
+use crate::schedule_file::{default_schedule_path, LoadedSchedule};
+use crate::scheduler::{occurrences_between, theme_at, ScheduleZone};
+use crate::state::{format_duration, SimulationState};
+use crate::theme::InterpolatableTheme;
+use crate::timeline::{DayTimeline, TimelineSource, TIMELINE_KEYS};
+use crate::{AppMode, AppView};
+use chrono::{Duration, Local, NaiveTime, Utc};
+use gpui::{div, prelude::*, rems, AnyElement, Context, IntoElement, SharedString};
 
 const SHOW_THEME_HINT_FOOTER: bool = true;
 
@@ -51,6 +63,55 @@ pub fn render_interactive_ui(
         "border",
     ];
 
+    let right_panel_children = if view.show_theme_diff {
+        // The diff is only recomputed when the themes or the sort change, not on
+        // every frame of a fade.
+        let source = (
+            app_state.start_theme_index,
+            app_state.end_theme_index,
+            view.diff_sort,
+        );
+        if view
+            .theme_diff
+            .as_ref()
+            .is_some_and(|(cached, _)| *cached != source)
+        {
+            view.theme_diff = None;
+        }
+        let (_, diff) = view.theme_diff.get_or_insert_with(|| {
+            let mut diff = ThemeDiff::between(
+                &start_theme.interpolatable_theme,
+                &end_theme.interpolatable_theme,
+            );
+            diff.sort_by(source.2);
+            (source, diff)
+        });
+        vec![render_theme_diff("theme-diff-list", diff, active_theme).into_any_element()]
+    } else {
+        key_colors
+            .iter()
+            .map(|&key| {
+                let start_hsla = start_theme
+                    .interpolatable_theme
+                    .0
+                    .get(key)
+                    .map_or(gpui::black(), |c| c.hsla);
+                let end_hsla = end_theme
+                    .interpolatable_theme
+                    .0
+                    .get(key)
+                    .map_or(gpui::black(), |c| c.hsla);
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(div().text_sm().child(key))
+                    .child(render_gradient_bar(start_hsla, end_hsla))
+                    .into_any_element()
+            })
+            .collect::<Vec<_>>()
+    };
+
     div()
         .track_focus(&view.root_focus_handle)
         .on_mouse_down(
@@ -75,8 +136,41 @@ pub fn render_interactive_ui(
                 .flex()
                 .justify_between()
                 .items_center()
-                .child(div().text_xl().child("Theme Transition Simulator")),
+                .child(div().text_xl().child("Theme Transition Simulator"))
+                .child(
+                    div()
+                        .flex()
+                        .gap_2()
+                        .child(render_app_mode_button(view, app_state.app_mode, cx))
+                        .when(view.show_theme_diff, |s| {
+                            s.child(render_button(
+                                "diff-sort-button",
+                                format!("Sort: {}", view.diff_sort.label()),
+                                None,
+                                &view.diff_sort_focus_handle,
+                                false,
+                                |view, _, _, cx| view.cycle_diff_sort(cx),
+                                cx,
+                            ))
+                        })
+                        .child(render_button(
+                            "diff-toggle-button",
+                            if view.show_theme_diff {
+                                "Show Gradients"
+                            } else {
+                                "Show Diff"
+                            },
+                            None,
+                            &view.diff_toggle_focus_handle,
+                            false,
+                            |view, _, _, cx| view.toggle_theme_diff(cx),
+                            cx,
+                        )),
+                ),
         )
+        .when_some(view.zed_settings_notice.clone(), |s, notice| {
+            s.child(render_zed_settings_notice(notice, cx))
+        })
         .child(
             // Main Content
             div()
@@ -103,6 +197,7 @@ pub fn render_interactive_ui(
                                 app_state.start_theme_index,
                                 view.start_dropdown_state.preview_index,
                                 &[app_state.end_theme_index],
+                                &view.start_dropdown_state.recommendations,
                                 is_running,
                                 active_theme,
                                 |view, _, _, cx| view.toggle_start_dropdown(cx),
@@ -129,6 +224,7 @@ pub fn render_interactive_ui(
                                 app_state.end_theme_index,
                                 view.end_dropdown_state.preview_index,
                                 &[app_state.start_theme_index],
+                                &view.end_dropdown_state.recommendations,
                                 is_running,
                                 active_theme,
                                 |view, _, _, cx| view.toggle_end_dropdown(cx),
@@ -158,6 +254,18 @@ pub fn render_interactive_ui(
                             active_theme,
                         )
                         .into_any_element(),
+                        render_button(
+                            "alpha-mode-button",
+                            format!("Alpha Blending: {}", app_state.alpha_mode.label()),
+                            None,
+                            &view.alpha_mode_focus_handle,
+                            is_running,
+                            |view, _, _, cx| {
+                                view.cycle_alpha_mode(cx);
+                            },
+                            cx,
+                        )
+                        .into_any_element(),
                         render_button(
                             "run-simulation-button",
                             if is_running {
@@ -174,6 +282,35 @@ pub fn render_interactive_ui(
                             cx,
                         )
                         .into_any_element(),
+                        div()
+                            .flex()
+                            .gap_2()
+                            .when(!is_running, |s| s.invisible())
+                            .child(
+                                div().flex_1().child(render_button(
+                                    "pause-simulation-button",
+                                    if view.simulation_state == SimulationState::Paused {
+                                        "Resume"
+                                    } else {
+                                        "Pause"
+                                    },
+                                    None,
+                                    &view.pause_focus_handle,
+                                    !is_running,
+                                    |view, _, _, cx| view.toggle_pause_simulation(cx),
+                                    cx,
+                                )),
+                            )
+                            .child(div().flex_1().child(render_button(
+                                "cancel-simulation-button",
+                                "Cancel",
+                                None,
+                                &view.cancel_focus_handle,
+                                !is_running,
+                                |view, _, _, cx| view.cancel_simulation(cx),
+                                cx,
+                            )))
+                            .into_any_element(),
                         div()
                             .id("simulation-status")
                             .h_6() // Fixed height to prevent layout shift
@@ -185,35 +322,12 @@ pub fn render_interactive_ui(
                             .into_any_element(),
                     ],
                 )))
-                .child(
-                    div().flex_1().child(render_panel(
-                        "right-panel",
-                        rems(0.5).into(), // gap_2
-                        active_theme,
-                        key_colors
-                            .iter()
-                            .map(|&key| {
-                                let start_hsla = start_theme
-                                    .interpolatable_theme
-                                    .0
-                                    .get(key)
-                                    .map_or(gpui::black(), |c| c.hsla);
-                                let end_hsla = end_theme
-                                    .interpolatable_theme
-                                    .0
-                                    .get(key)
-                                    .map_or(gpui::black(), |c| c.hsla);
-                                div()
-                                    .flex()
-                                    .flex_col()
-                                    .gap_1()
-                                    .child(div().text_sm().child(key))
-                                    .child(render_gradient_bar(start_hsla, end_hsla))
-                                    .into_any_element()
-                            })
-                            .collect::<Vec<_>>(),
-                    )),
-                ),
+                .child(div().flex_1().child(render_panel(
+                    "right-panel",
+                    rems(0.5).into(), // gap_2
+                    active_theme,
+                    right_panel_children,
+                ))),
         )
         .when(SHOW_THEME_HINT_FOOTER, |parent| {
             parent.child(
@@ -230,3 +344,629 @@ pub fn render_interactive_ui(
             )
         })
 }
+
+fn render_app_mode_button(
+    view: &crate::AppView,
+    app_mode: AppMode,
+    cx: &mut Context<crate::AppView>,
+) -> impl IntoElement {
+    render_button(
+        "app-mode-button",
+        match app_mode {
+            AppMode::Scheduler => "Open Simulator",
+            AppMode::Interactive => "Open Scheduler",
+        },
+        None,
+        &view.app_mode_focus_handle,
+        false,
+        |view, _, _, cx| view.toggle_app_mode(cx),
+        cx,
+    )
+}
+
+/// Why the themes Zed is set to weren't preselected, shown below either mode's
+/// header until dismissed.
+fn render_zed_settings_notice(
+    notice: SharedString,
+    cx: &mut Context<crate::AppView>,
+) -> impl IntoElement {
+    div()
+        .flex()
+        .justify_between()
+        .items_center()
+        .gap_2()
+        .text_sm()
+        .child(notice)
+        .child(
+            div()
+                .id("dismiss-zed-settings-notice")
+                .underline()
+                .cursor_pointer()
+                .child("Dismiss")
+                .on_click(cx.listener(|view, _, _, cx| view.dismiss_zed_settings_notice(cx))),
+        )
+}
+
+/// The `AppMode::Scheduler` dashboard: what is showing and what comes next,
+/// today's schedule, and controls for the running scheduler.
+pub fn render_scheduler_ui(
+    view: &mut crate::AppView,
+    window: &mut gpui::Window,
+    cx: &mut Context<crate::AppView>,
+) -> impl IntoElement {
+    let app_state = cx.global::<crate::AppState>().clone();
+    let start_focused = view.start_dropdown_state.focus_handle.is_focused(window);
+    let has_scheduler = view.scheduler_handle.is_some();
+    let now = view
+        .scheduler_handle
+        .as_ref()
+        .map_or_else(Utc::now, |handle| handle.clock().now());
+
+    let profile = app_state.schedule.as_ref().map(LoadedSchedule::active);
+    let entries = profile.map(|profile| profile.schedule_for(app_state.appearance));
+    let sample = entries
+        .as_ref()
+        .filter(|_| has_scheduler)
+        .and_then(|entries| theme_at(entries, now));
+    // While a timeline moment is previewed, the dashboard wears its theme.
+    let preview = view.timeline_preview.and_then(|instant| {
+        let sample = theme_at(entries.as_ref()?, instant)?;
+        Some((instant, sample.theme))
+    });
+    let active_theme = preview
+        .as_ref()
+        .map_or(&app_state.active_theme, |(_, theme)| theme);
+    let surface_background = active_theme.0.get("surface.background").unwrap().hsla;
+    let text_color = active_theme.0.get("text").unwrap().hsla;
+    let theme_name = |index: usize| {
+        profile.map_or(String::new(), |profile| {
+            profile.theme_name(index, app_state.appearance).to_string()
+        })
+    };
+
+    let current_name = match (&view.simulation_state, &sample) {
+        (SimulationState::Overridden { .. }, _) | (_, None) => {
+            app_state.themes[app_state.start_theme_index].name.clone()
+        }
+        (_, Some(sample)) => theme_name(sample.current_index),
+    };
+    let next = sample.as_ref().map(|sample| {
+        format!(
+            "{} at {}",
+            theme_name(sample.next_index),
+            sample.next_at.with_timezone(&Local).format("%H:%M")
+        )
+    });
+
+    // Everything taking effect on the local calendar day.
+    let today = ScheduleZone::SystemLocal.date_of(now);
+    let day_start = ScheduleZone::SystemLocal.resolve(today.and_time(NaiveTime::MIN));
+    let day_end =
+        ScheduleZone::SystemLocal.resolve((today + Duration::days(1)).and_time(NaiveTime::MIN));
+    let rows = entries
+        .as_ref()
+        .map(|entries| {
+            occurrences_between(entries, day_start, day_end)
+                .into_iter()
+                .map(|(index, at)| ScheduleRow {
+                    time: at.with_timezone(&Local).format("%H:%M").to_string(),
+                    theme_name: theme_name(index),
+                    fade: (entries[index].fade_duration > Duration::zero())
+                        .then(|| format_duration(entries[index].fade_duration)),
+                    is_current: sample.as_ref().is_some_and(|sample| {
+                        sample.current_index == index && sample.current_at == at
+                    }),
+                    is_past: at <= now,
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    // The timeline is only re-sampled when the day, profile or key changes.
+    let timeline_key = TIMELINE_KEYS[view.timeline_key];
+    if let (Some(schedule), Some(entries)) = (&app_state.schedule, &entries) {
+        let source = TimelineSource {
+            profile: schedule.active_profile,
+            appearance: app_state.appearance,
+            day: today,
+            key: timeline_key,
+        };
+        if view
+            .timeline
+            .as_ref()
+            .is_none_or(|(cached, _)| *cached != source)
+        {
+            view.timeline = Some((source, DayTimeline::build(entries, today, timeline_key)));
+        }
+    }
+
+    let status = match &view.simulation_state {
+        SimulationState::Fading { progress } => div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(format!("Fading... {:.0}%", progress * 100.0))
+            .child(render_progress_bar(*progress, active_theme))
+            .into_any_element(),
+        SimulationState::Idle if !has_scheduler => div().child("Not running.").into_any_element(),
+        state => div().child(state.display()).into_any_element(),
+    };
+
+    let left_panel_children = vec![
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(div().text_sm().child("Now"))
+            .child(div().text_lg().child(current_name))
+            .into_any_element(),
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(div().text_sm().child("Next"))
+            .child(
+                div()
+                    .text_lg()
+                    .child(next.unwrap_or_else(|| "—".to_string())),
+            )
+            .into_any_element(),
+        div()
+            .id("scheduler-status")
+            .h_10() // Fixed height so the progress bar doesn't shift the layout
+            .text_sm()
+            .child(status)
+            .into_any_element(),
+        div()
+            .flex()
+            .gap_2()
+            .child(div().flex_1().child(render_button(
+                "pause-scheduler-button",
+                if view.simulation_state == SimulationState::Paused {
+                    "Resume"
+                } else {
+                    "Pause"
+                },
+                None,
+                &view.pause_focus_handle,
+                !has_scheduler,
+                |view, _, _, cx| view.toggle_pause_simulation(cx),
+                cx,
+            )))
+            .child(div().flex_1().child(render_button(
+                "skip-scheduler-button",
+                "Skip to Next",
+                None,
+                &view.skip_focus_handle,
+                !has_scheduler,
+                |view, _, _, cx| view.skip_to_next(cx),
+                cx,
+            )))
+            .into_any_element(),
+        render_form_field(
+            "Override Theme:",
+            None,
+            render_dropdown(
+                "override-theme-selector",
+                "override-theme-button",
+                "override-theme",
+                "override-theme-scroll",
+                view.start_dropdown_state.is_open,
+                &view.start_dropdown_state.focus_handle,
+                &view.start_dropdown_state.scroll_handle,
+                &app_state.themes,
+                app_state.start_theme_index,
+                view.start_dropdown_state.preview_index,
+                &[],
+                &[],
+                !has_scheduler,
+                active_theme,
+                |view, _, _, cx| view.toggle_start_dropdown(cx),
+                |index, view, _, _, cx| view.select_start_theme(index, cx),
+                cx,
+            ),
+            !has_scheduler,
+            start_focused,
+            active_theme,
+        )
+        .into_any_element(),
+        render_button(
+            "override-until-button",
+            format!("Hold: {}", view.override_until.label()),
+            None,
+            &view.override_until_focus_handle,
+            !has_scheduler,
+            |view, _, _, cx| view.cycle_override_until(cx),
+            cx,
+        )
+        .into_any_element(),
+    ];
+
+    let mut right_panel_children = Vec::new();
+    match &app_state.schedule {
+        _ if view.schedule_editor.is_some() => {
+            right_panel_children = render_schedule_editor(view, window, active_theme, cx);
+        }
+        Some(schedule) => {
+            if schedule.profiles.len() > 1 {
+                right_panel_children.push(
+                    render_profile_selector(
+                        &schedule.profiles,
+                        schedule.active_profile,
+                        active_theme,
+                        |index, view, _, _, cx| view.switch_profile(index, cx),
+                        cx,
+                    )
+                    .into_any_element(),
+                );
+            }
+            right_panel_children.push(
+                div()
+                    .flex()
+                    .justify_between()
+                    .items_center()
+                    .child(div().text_lg().child("Today"))
+                    .child(render_button(
+                        "timeline-key-button",
+                        format!("Color: {}", timeline_key),
+                        None,
+                        &view.timeline_key_focus_handle,
+                        false,
+                        |view, _, _, cx| view.cycle_timeline_key(cx),
+                        cx,
+                    ))
+                    .into_any_element(),
+            );
+            if let Some((_, timeline)) = &view.timeline {
+                let now_fraction = (timeline.start..timeline.end)
+                    .contains(&now)
+                    .then(|| timeline.fraction_of(now));
+                right_panel_children.push(
+                    render_timeline(
+                        timeline,
+                        now_fraction,
+                        preview
+                            .as_ref()
+                            .map(|(instant, _)| timeline.fraction_of(*instant)),
+                        active_theme,
+                        |slice, view, _, _, cx| view.preview_timeline_slice(slice, cx),
+                        cx,
+                    )
+                    .into_any_element(),
+                );
+            }
+            if let Some((instant, _)) = &preview {
+                right_panel_children.push(
+                    div()
+                        .flex()
+                        .gap_2()
+                        .text_sm()
+                        .child(format!(
+                            "Previewing {}.",
+                            instant.with_timezone(&Local).format("%H:%M")
+                        ))
+                        .child(
+                            div()
+                                .id("timeline-back-to-live")
+                                .underline()
+                                .cursor_pointer()
+                                .child("Back to live")
+                                .on_click(
+                                    cx.listener(|view, _, _, cx| view.clear_timeline_preview(cx)),
+                                ),
+                        )
+                        .into_any_element(),
+                );
+            }
+            right_panel_children.push(render_schedule_list(&rows, active_theme).into_any_element());
+            if !schedule.active().diagnostics.is_empty() {
+                right_panel_children.push(
+                    render_schedule_diagnostics(&schedule.active().diagnostics, active_theme)
+                        .into_any_element(),
+                );
+            }
+        }
+        None => right_panel_children.push(
+            div()
+                .child(match default_schedule_path() {
+                    Some(path) => format!(
+                        "No schedule loaded. Pass --schedule <path>, or create {}.",
+                        path.display()
+                    ),
+                    None => "No schedule loaded. Pass --schedule <path>, or set HOME to create \
+                             one."
+                        .to_string(),
+                })
+                .into_any_element(),
+        ),
+    }
+
+    div()
+        .track_focus(&view.root_focus_handle)
+        .on_mouse_down(
+            gpui::MouseButton::Left,
+            cx.listener(|view, _, window, cx| {
+                view.focus_root(window, cx);
+            }),
+        )
+        .key_context("SchedulerUI")
+        .on_action(cx.listener(AppView::on_focus_next))
+        .on_action(cx.listener(AppView::on_focus_prev))
+        .on_action(cx.listener(AppView::on_close_dropdowns))
+        .size_full()
+        .bg(surface_background)
+        .text_color(text_color)
+        .p_4()
+        .gap_4()
+        .child(
+            // Header
+            div()
+                .flex()
+                .justify_between()
+                .items_center()
+                .child(div().text_xl().child("Theme Scheduler"))
+                .child(
+                    div()
+                        .flex()
+                        .gap_2()
+                        .child(render_button(
+                            "edit-schedule-button",
+                            if view.schedule_editor.is_some() {
+                                "Close Editor"
+                            } else {
+                                "Edit Schedule"
+                            },
+                            None,
+                            &view.edit_schedule_focus_handle,
+                            // With no schedule and no HOME there is nowhere to
+                            // save one; the panel below says so.
+                            view.schedule_editor.is_none()
+                                && app_state.schedule.is_none()
+                                && default_schedule_path().is_none(),
+                            |view, _, _, cx| view.toggle_schedule_editor(cx),
+                            cx,
+                        ))
+                        .child(render_app_mode_button(view, app_state.app_mode, cx)),
+                ),
+        )
+        .when_some(view.zed_settings_notice.clone(), |s, notice| {
+            s.child(render_zed_settings_notice(notice, cx))
+        })
+        .child(
+            // Main Content
+            div()
+                .flex_1()
+                .flex()
+                .gap_4()
+                .child(div().w_64().flex_shrink_0().child(render_panel(
+                    "scheduler-left-panel",
+                    rems(1.0).into(), // gap_4
+                    active_theme,
+                    left_panel_children,
+                )))
+                .child(div().flex_1().child(render_panel(
+                    "scheduler-right-panel",
+                    rems(0.5).into(), // gap_2
+                    active_theme,
+                    right_panel_children,
+                ))),
+        )
+}
+
+/// The schedule editor's rows and controls, shown in place of the dashboard's
+/// right panel while it is open.
+fn render_schedule_editor(
+    view: &crate::AppView,
+    window: &gpui::Window,
+    active_theme: &InterpolatableTheme,
+    cx: &mut Context<crate::AppView>,
+) -> Vec<AnyElement> {
+    let Some(editor) = &view.schedule_editor else {
+        return Vec::new();
+    };
+    let themes = cx.global::<crate::AppState>().themes.clone();
+    let border_color = active_theme.0.get("border").unwrap().hsla;
+    let row_count = editor.rows.len();
+
+    let rows = editor.rows.iter().enumerate().map(|(index, row)| {
+        let theme_focused = row.theme_dropdown.focus_handle.is_focused(window);
+        let time_focused = row.time_input.read(cx).focus_handle.is_focused(window);
+        let fade_focused = row.fade_input.read(cx).focus_handle.is_focused(window);
+        let theme_index = themes
+            .iter()
+            .position(|theme| theme.name == row.theme)
+            .unwrap_or(0);
+
+        div()
+            .id(("schedule-editor-row", index))
+            .flex()
+            .flex_col()
+            .gap_2()
+            .p_2()
+            .border_1()
+            .border_color(border_color)
+            .rounded_md()
+            .child(
+                div()
+                    .flex()
+                    .justify_between()
+                    .items_center()
+                    .child(format!("Entry {}", index + 1))
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .child(render_button(
+                                "editor-move-up-button",
+                                "Move Up",
+                                None,
+                                &row.move_up_focus_handle,
+                                index == 0,
+                                move |view, _, _, cx| view.move_editor_row(index, true, cx),
+                                cx,
+                            ))
+                            .child(render_button(
+                                "editor-move-down-button",
+                                "Move Down",
+                                None,
+                                &row.move_down_focus_handle,
+                                index + 1 == row_count,
+                                move |view, _, _, cx| view.move_editor_row(index, false, cx),
+                                cx,
+                            ))
+                            .child(render_button(
+                                "editor-remove-button",
+                                "Remove",
+                                None,
+                                &row.remove_focus_handle,
+                                false,
+                                move |view, _, _, cx| view.remove_editor_row(index, cx),
+                                cx,
+                            )),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .items_start()
+                    .child(div().flex_1().child(render_form_field(
+                        "Theme:",
+                        row.theme_message.clone(),
+                        render_dropdown(
+                            "editor-theme-selector",
+                            "editor-theme-button",
+                            "editor-theme",
+                            "editor-theme-scroll",
+                            row.theme_dropdown.is_open,
+                            &row.theme_dropdown.focus_handle,
+                            &row.theme_dropdown.scroll_handle,
+                            &themes,
+                            theme_index,
+                            row.theme_dropdown.preview_index,
+                            &[],
+                            &[],
+                            false,
+                            active_theme,
+                            move |view, _, _, cx| view.toggle_editor_dropdown(index, cx),
+                            move |theme_index, view, _, _, cx| {
+                                view.select_editor_theme(index, theme_index, cx)
+                            },
+                            cx,
+                        ),
+                        false,
+                        theme_focused,
+                        active_theme,
+                    )))
+                    .child(div().w_40().child(render_form_field(
+                        "Time:",
+                        row.time_message.clone(),
+                        row.time_input.clone(),
+                        false,
+                        time_focused,
+                        active_theme,
+                    )))
+                    .child(div().w_32().child(render_form_field(
+                        "Fade:",
+                        row.fade_message.clone(),
+                        row.fade_input.clone(),
+                        false,
+                        fade_focused,
+                        active_theme,
+                    )))
+                    .child(div().w_40().child(render_form_field(
+                        "Easing:",
+                        None,
+                        render_button(
+                            "editor-easing-button",
+                            row.easing.label(),
+                            None,
+                            &row.easing_focus_handle,
+                            false,
+                            move |view, _, _, cx| view.cycle_editor_easing(index, cx),
+                            cx,
+                        ),
+                        false,
+                        false,
+                        active_theme,
+                    ))),
+            )
+            .into_any_element()
+    });
+
+    let mut children = vec![div()
+        .flex()
+        .flex_col()
+        .gap_1()
+        .child(div().text_lg().child("Edit Schedule"))
+        .child(div().text_sm().child(editor.path.display().to_string()))
+        .into_any_element()];
+    if editor.follows_appearance {
+        children.push(
+            div()
+                .child(
+                    "This profile follows the desktop appearance; its light and dark themes \
+                     are set in the file.",
+                )
+                .into_any_element(),
+        );
+    } else {
+        children.push(
+            div()
+                .id("schedule-editor-rows")
+                .flex_1()
+                .flex()
+                .flex_col()
+                .gap_2()
+                .overflow_y_scroll()
+                .children(rows.collect::<Vec<_>>())
+                .into_any_element(),
+        );
+    }
+    if !editor.diagnostics.is_empty() {
+        children.push(
+            render_schedule_diagnostics(&editor.diagnostics, active_theme).into_any_element(),
+        );
+    }
+    children.push(
+        div()
+            .flex()
+            .gap_2()
+            .items_center()
+            .child(render_button(
+                "editor-add-button",
+                "Add Entry",
+                None,
+                &editor.add_focus_handle,
+                editor.follows_appearance,
+                |view, _, _, cx| view.add_editor_row(cx),
+                cx,
+            ))
+            .child(render_button(
+                "editor-save-button",
+                "Save",
+                None,
+                &editor.save_focus_handle,
+                editor.follows_appearance || editor.has_errors,
+                |view, _, _, cx| view.save_schedule_editor(cx),
+                cx,
+            ))
+            .child(render_button(
+                "editor-reload-button",
+                "Reload",
+                None,
+                &editor.reload_focus_handle,
+                false,
+                |view, _, _, cx| view.reload_schedule_editor(cx),
+                cx,
+            ))
+            .children(
+                editor
+                    .status
+                    .clone()
+                    .map(|status| div().text_sm().child(status)),
+            )
+            .into_any_element(),
+    );
+    children
+}
@@ -0,0 +1,188 @@
+use chrono::{DateTime, Duration, Utc};
+use std::fmt;
+
+use crate::scheduler::{next_occurrence, next_reachable, theme_at, ScheduleEntry};
+
+// --- SCHEDULE VALIDATION ---
+//
+// `run_loop` copes with odd schedules (it jumps into a fade that should have
+// started before the previous entry, and plays duplicate times in list order),
+// but the result is rarely what the author meant. These checks explain why.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    /// The schedule is not started while any error remains.
+    Error,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Which part of the schedule the problem is in, e.g. `entries[2].fade`.
+    pub field: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn error(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{}: {}: {}", severity, self.field, self.message)
+    }
+}
+
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error)
+}
+
+/// How far ahead transitions are checked. A fortnight covers every weekday
+/// variant twice, and the sunrise/sunset drift over it is small.
+const VALIDATION_DAYS: i64 = 14;
+
+/// Checks `schedule` for entries that never occur, duplicate times, and fades
+/// that overlap or cross the entry before them, over the fortnight from `from`.
+/// `labels` names each entry in messages, e.g. `entries[2]`.
+pub fn validate_schedule(
+    schedule: &[ScheduleEntry],
+    labels: &[String],
+    from: DateTime<Utc>,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    if schedule.is_empty() {
+        return diagnostics;
+    }
+    let label = |index: usize| {
+        labels
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| format!("entries[{}]", index))
+    };
+
+    for (index, entry) in schedule.iter().enumerate() {
+        if next_occurrence(entry, from, true).is_none() {
+            diagnostics.push(Diagnostic::warning(
+                format!("{}.time", label(index)),
+                format!(
+                    "{} never occurs within a year, so it is skipped",
+                    entry.time
+                ),
+            ));
+        }
+    }
+
+    let Some(sample) = theme_at(schedule, from) else {
+        diagnostics.push(Diagnostic::error(
+            "entries",
+            "no entry ever occurs, so there is nothing to schedule",
+        ));
+        return diagnostics;
+    };
+
+    // Walk the transitions in order, comparing each with the one before it.
+    let until = from + Duration::days(VALIDATION_DAYS);
+    let (mut previous_index, mut previous_at) = (sample.current_index, sample.current_at);
+    while let Some((index, at)) = next_reachable(schedule, previous_index, previous_at) {
+        if at > until {
+            break;
+        }
+        let (entry, previous) = (&schedule[index], &schedule[previous_index]);
+        let fade_start = at - entry.fade_duration;
+
+        let diagnostic = if at == previous_at && index != previous_index {
+            Some(Diagnostic::error(
+                format!("{}.time", label(index)),
+                format!(
+                    "takes effect at the same moment as {}, whose theme is never shown",
+                    label(previous_index)
+                ),
+            ))
+        } else if previous.fade_duration > Duration::zero()
+            && fade_start < previous_at - previous.fade_duration
+        {
+            Some(Diagnostic::error(
+                format!("{}.fade", label(index)),
+                format!(
+                    "the fade starts before the fade into {} does, crossing that entry entirely",
+                    label(previous_index)
+                ),
+            ))
+        } else if fade_start < previous_at && previous.fade_duration > Duration::zero() {
+            Some(Diagnostic::warning(
+                format!("{}.fade", label(index)),
+                format!(
+                    "the fade overlaps the fade into {} by {}",
+                    label(previous_index),
+                    describe(previous_at - fade_start)
+                ),
+            ))
+        } else if fade_start < previous_at {
+            Some(Diagnostic::warning(
+                format!("{}.fade", label(index)),
+                format!(
+                    "the fade starts {} before {} takes effect, so it begins part-way through",
+                    describe(previous_at - fade_start),
+                    label(previous_index)
+                ),
+            ))
+        } else if fade_start == previous_at && entry.fade_duration > Duration::zero() {
+            Some(Diagnostic::warning(
+                format!("{}.fade", label(index)),
+                format!(
+                    "the fade starts the moment {} takes effect, so its theme is never shown \
+                     unblended",
+                    label(previous_index)
+                ),
+            ))
+        } else {
+            None
+        };
+        // Solar times drift day to day, so report each field once.
+        if let Some(diagnostic) = diagnostic {
+            if !diagnostics
+                .iter()
+                .any(|known| known.field == diagnostic.field)
+            {
+                diagnostics.push(diagnostic);
+            }
+        }
+
+        (previous_index, previous_at) = (index, at);
+    }
+
+    diagnostics.sort_by_key(|diagnostic| std::cmp::Reverse(diagnostic.severity));
+    diagnostics
+}
+
+fn describe(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    if minutes >= 60 {
+        format!("{}h{:02}m", minutes / 60, minutes % 60)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", duration.num_seconds())
+    }
+}
@@ -0,0 +1,528 @@
+use anyhow::{anyhow, Context, Result};
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+use tracing::{info, warn};
+
+use crate::{
+    appearance::Appearance,
+    schedule_file::write_atomically,
+    scheduler::{SchedulerEvent, Transition, TransitionKind},
+};
+
+// --- ZED SETTINGS ---
+//
+// Zed reads its theme from `~/.config/zed/settings.json`, either as a name or
+// as a light/dark pair:
+//
+//     "theme": "One Dark"
+//     "theme": { "mode": "system", "light": "One Light", "dark": "One Dark" }
+//
+// The file is JSONC, with comments and trailing commas, and is the user's own,
+// so it is never re-serialized: only the characters of the theme value are
+// replaced, and everything around them is kept byte for byte. The same walk
+// reads the setting at startup, to start from the themes Zed is showing.
+
+/// Where Zed keeps its user settings.
+pub fn zed_settings_path() -> Option<PathBuf> {
+    let home = std::env::var("HOME").ok()?;
+    Some(PathBuf::from(home).join(".config/zed/settings.json"))
+}
+
+/// Walks JSONC text just far enough to find values by key.
+struct Scanner<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn at(text: &'a str, position: usize) -> Self {
+        Self { text, position }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.position).copied()
+    }
+
+    /// Skips whitespace and comments.
+    fn skip_trivia(&mut self) {
+        let bytes = self.text.as_bytes();
+        loop {
+            match (self.peek(), bytes.get(self.position + 1)) {
+                (Some(c), _) if c.is_ascii_whitespace() => self.position += 1,
+                (Some(b'/'), Some(b'/')) => {
+                    self.position = self.text[self.position..]
+                        .find('\n')
+                        .map_or(self.text.len(), |end| self.position + end);
+                }
+                (Some(b'/'), Some(b'*')) => {
+                    self.position = self.text[self.position + 2..]
+                        .find("*/")
+                        .map_or(self.text.len(), |end| self.position + 2 + end + 2);
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Consumes `byte` after any trivia.
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        self.skip_trivia();
+        if self.peek() != Some(byte) {
+            return Err(anyhow!(
+                "expected '{}' at byte {}",
+                byte as char,
+                self.position
+            ));
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    /// Consumes a string literal, returning its range including the quotes.
+    fn string(&mut self) -> Result<Range<usize>> {
+        self.skip_trivia();
+        let start = self.position;
+        self.expect(b'"')?;
+        let bytes = self.text.as_bytes();
+        while let Some(&c) = bytes.get(self.position) {
+            self.position += if c == b'\\' { 2 } else { 1 };
+            if c == b'"' {
+                return Ok(start..self.position);
+            }
+        }
+        Err(anyhow!("unterminated string at byte {}", start))
+    }
+
+    /// Consumes any value, returning its range.
+    fn value(&mut self) -> Result<Range<usize>> {
+        self.skip_trivia();
+        let start = self.position;
+        match self.peek() {
+            Some(b'"') => return self.string(),
+            Some(open @ (b'{' | b'[')) => {
+                let close = if open == b'{' { b'}' } else { b']' };
+                self.position += 1;
+                loop {
+                    self.skip_trivia();
+                    match self.peek() {
+                        Some(c) if c == close => {
+                            self.position += 1;
+                            break;
+                        }
+                        Some(b',') => self.position += 1,
+                        Some(_) if open == b'{' => {
+                            self.string()?;
+                            self.expect(b':')?;
+                            self.value()?;
+                        }
+                        Some(_) => {
+                            self.value()?;
+                        }
+                        None => {
+                            return Err(anyhow!("unclosed '{}' at byte {}", open as char, start))
+                        }
+                    }
+                }
+            }
+            _ => {
+                // A number, `true`, `false` or `null`.
+                let bytes = self.text.as_bytes();
+                while bytes
+                    .get(self.position)
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, b'-' | b'+' | b'.'))
+                {
+                    self.position += 1;
+                }
+                if self.position == start {
+                    return Err(anyhow!("expected a value at byte {}", start));
+                }
+            }
+        }
+        Ok(start..self.position)
+    }
+}
+
+/// The range of the object the text consists of.
+fn root_object(text: &str) -> Result<Range<usize>> {
+    let mut scanner = Scanner::at(text, 0);
+    let range = scanner.value()?;
+    if !text[range.clone()].starts_with('{') {
+        return Err(anyhow!("the settings are not a JSON object"));
+    }
+    Ok(range)
+}
+
+/// The range of the value of `object`'s member named `key`, if it has one.
+fn find_member(text: &str, object: &Range<usize>, key: &str) -> Result<Option<Range<usize>>> {
+    let mut scanner = Scanner::at(text, object.start);
+    scanner.expect(b'{')?;
+    loop {
+        scanner.skip_trivia();
+        match scanner.peek() {
+            Some(b'}') | None => return Ok(None),
+            Some(b',') => scanner.position += 1,
+            Some(_) => {
+                let name = scanner.string()?;
+                scanner.expect(b':')?;
+                let value = scanner.value()?;
+                if parse_string(text, &name).as_deref() == Some(key) {
+                    return Ok(Some(value));
+                }
+            }
+        }
+    }
+}
+
+/// The string literal at `range`, unescaped, if it is one.
+fn parse_string(text: &str, range: &Range<usize>) -> Option<String> {
+    serde_json::from_str(&text[range.clone()]).ok()
+}
+
+/// Adds `"key": value` as the first member of the object at `object`,
+/// indented like the members already there.
+fn insert_member(text: &str, object: &Range<usize>, key: &str, value: &str) -> String {
+    let line_start = |position: usize| text[..position].rfind('\n').map_or(0, |i| i + 1);
+    let indent_of = |position: usize| {
+        let start = line_start(position);
+        text[start..]
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect::<String>()
+    };
+
+    let mut scanner = Scanner::at(text, object.start + 1);
+    scanner.skip_trivia();
+    let is_empty = scanner.peek() == Some(b'}');
+    let key = serde_json::to_string(key).unwrap_or_default();
+    let after_brace = object.start + 1;
+    let member = if is_empty {
+        let outer = indent_of(object.start);
+        format!("\n{}  {}: {}\n{}", outer, key, value, outer)
+    } else {
+        // Members on the brace's own line, as in `{ "a": 1 }`, stay inline.
+        let first = scanner.position;
+        if text[after_brace..first].contains('\n') {
+            format!("\n{}{}: {},", indent_of(first), key, value)
+        } else {
+            format!(" {}: {},", key, value)
+        }
+    };
+    let mut result = text.to_string();
+    result.insert_str(after_brace, &member);
+    result
+}
+
+fn replace(text: &str, range: Range<usize>, replacement: &str) -> String {
+    let mut result = text.to_string();
+    result.replace_range(range, replacement);
+    result
+}
+
+/// `text` with its theme set to `theme`. In the light/dark form, the theme
+/// Zed shows for `mode` is replaced; with `"mode": "system"` (or no mode)
+/// that is the one for the desktop's current `appearance`.
+pub fn set_theme(text: &str, theme: &str, appearance: Appearance) -> Result<String> {
+    if text.trim().is_empty() {
+        return Ok(format!(
+            "{{\n  \"theme\": {}\n}}\n",
+            serde_json::to_string(theme)?
+        ));
+    }
+    let root = root_object(text)?;
+    let name = serde_json::to_string(theme)?;
+    let Some(setting) = find_member(text, &root, "theme")? else {
+        return Ok(insert_member(text, &root, "theme", &name));
+    };
+
+    match text.as_bytes()[setting.start] {
+        b'"' => Ok(replace(text, setting, &name)),
+        b'{' => {
+            let mode =
+                find_member(text, &setting, "mode")?.and_then(|mode| parse_string(text, &mode));
+            let field = match mode.as_deref() {
+                Some("light") => "light",
+                Some("dark") => "dark",
+                _ => match appearance {
+                    Appearance::Light => "light",
+                    Appearance::Dark => "dark",
+                },
+            };
+            Ok(match find_member(text, &setting, field)? {
+                Some(value) => replace(text, value, &name),
+                None => insert_member(text, &setting, field, &name),
+            })
+        }
+        _ => Err(anyhow!(
+            "\"theme\" is neither a name nor a light/dark object"
+        )),
+    }
+}
+
+/// The theme a settings file configures.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ThemeSetting {
+    /// `"theme": "One Dark"`
+    Name(String),
+    /// `"theme": { "mode": ..., "light": ..., "dark": ... }`; any of them may
+    /// be missing.
+    ByAppearance {
+        mode: Option<String>,
+        light: Option<String>,
+        dark: Option<String>,
+    },
+}
+
+impl ThemeSetting {
+    /// The theme Zed shows for the desktop's `appearance`, then the other half
+    /// of a light/dark pair.
+    pub fn shown_and_other(&self, appearance: Appearance) -> (Option<&str>, Option<&str>) {
+        match self {
+            Self::Name(name) => (Some(name), None),
+            Self::ByAppearance { mode, light, dark } => {
+                let shows_light = match mode.as_deref() {
+                    Some("light") => true,
+                    Some("dark") => false,
+                    _ => appearance == Appearance::Light,
+                };
+                if shows_light {
+                    (light.as_deref(), dark.as_deref())
+                } else {
+                    (dark.as_deref(), light.as_deref())
+                }
+            }
+        }
+    }
+}
+
+/// The theme setting in `text`, if it has one.
+pub fn read_theme_setting(text: &str) -> Result<Option<ThemeSetting>> {
+    if text.trim().is_empty() {
+        return Ok(None);
+    }
+    let root = root_object(text)?;
+    let Some(setting) = find_member(text, &root, "theme")? else {
+        return Ok(None);
+    };
+    match text.as_bytes()[setting.start] {
+        b'"' => Ok(parse_string(text, &setting).map(ThemeSetting::Name)),
+        b'{' => {
+            let field = |key| -> Result<Option<String>> {
+                Ok(find_member(text, &setting, key)?.and_then(|value| parse_string(text, &value)))
+            };
+            Ok(Some(ThemeSetting::ByAppearance {
+                mode: field("mode")?,
+                light: field("light")?,
+                dark: field("dark")?,
+            }))
+        }
+        _ => Err(anyhow!(
+            "\"theme\" is neither a name nor a light/dark object"
+        )),
+    }
+}
+
+/// Reads the theme setting from the settings file at `path`; a missing file
+/// has none.
+pub fn read_theme_setting_file(path: &Path) -> Result<Option<ThemeSetting>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {:?}", path)),
+    };
+    read_theme_setting(&text).with_context(|| format!("Failed to parse {:?}", path))
+}
+
+/// Sets the theme in the settings file at `path`, creating the file if
+/// needed. Only the `theme` value changes; the rest of the file is left as it
+/// was, comments and formatting included.
+pub fn write_theme(path: &Path, theme: &str, appearance: Appearance) -> Result<()> {
+    // Write through a symlinked settings file (kept with dotfiles, say)
+    // rather than replacing the link.
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {:?}", path)),
+    };
+    let updated = set_theme(&text, theme, appearance)
+        .with_context(|| format!("Failed to edit {:?}", path))?;
+    if updated != text {
+        write_atomically(&path, &updated).with_context(|| format!("Failed to write {:?}", path))?;
+    }
+    Ok(())
+}
+
+/// Keeps Zed's theme setting in step with a scheduler (`--write-zed-settings`).
+#[derive(Clone, Debug)]
+pub struct ZedSettingsWriter {
+    pub path: PathBuf,
+}
+
+impl ZedSettingsWriter {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Writes the theme of each `ThemeApplied` transition; other events are
+    /// ignored. Failures are logged.
+    pub fn on_event(&self, event: &SchedulerEvent, appearance: Appearance) {
+        let SchedulerEvent::Transition(Transition {
+            kind: TransitionKind::ThemeApplied,
+            theme,
+            ..
+        }) = event
+        else {
+            return;
+        };
+        match write_theme(&self.path, &theme.name, appearance) {
+            Ok(()) => info!("Set the Zed theme to '{}'.", theme.name),
+            Err(e) => warn!("{:#}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(text: &str, appearance: Appearance) -> String {
+        set_theme(text, "Gruvbox Dark", appearance).unwrap()
+    }
+
+    #[test]
+    fn replaces_a_theme_name() {
+        assert_eq!(
+            set("{\n  \"theme\": \"One Light\"\n}\n", Appearance::Light),
+            "{\n  \"theme\": \"Gruvbox Dark\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn replaces_the_side_the_mode_shows() {
+        let text = "{\n  \"theme\": {\n    \"mode\": \"light\",\n    \"light\": \"One Light\",\n    \"dark\": \"One Dark\"\n  }\n}\n";
+        assert_eq!(
+            set(text, Appearance::Dark),
+            "{\n  \"theme\": {\n    \"mode\": \"light\",\n    \"light\": \"Gruvbox Dark\",\n    \"dark\": \"One Dark\"\n  }\n}\n"
+        );
+
+        let text = "{ \"theme\": { \"mode\": \"dark\", \"light\": \"One Light\", \"dark\": \"One Dark\" } }";
+        assert_eq!(
+            set(text, Appearance::Light),
+            "{ \"theme\": { \"mode\": \"dark\", \"light\": \"One Light\", \"dark\": \"Gruvbox Dark\" } }"
+        );
+    }
+
+    #[test]
+    fn system_mode_follows_the_appearance() {
+        let text = "{ \"theme\": { \"mode\": \"system\", \"light\": \"One Light\", \"dark\": \"One Dark\" } }";
+        assert_eq!(
+            set(text, Appearance::Light),
+            "{ \"theme\": { \"mode\": \"system\", \"light\": \"Gruvbox Dark\", \"dark\": \"One Dark\" } }"
+        );
+        assert_eq!(
+            set(text, Appearance::Dark),
+            "{ \"theme\": { \"mode\": \"system\", \"light\": \"One Light\", \"dark\": \"Gruvbox Dark\" } }"
+        );
+    }
+
+    #[test]
+    fn inserts_a_missing_side() {
+        assert_eq!(
+            set("{ \"theme\": { \"mode\": \"system\", \"light\": \"One Light\" } }", Appearance::Dark),
+            "{ \"theme\": { \"dark\": \"Gruvbox Dark\", \"mode\": \"system\", \"light\": \"One Light\" } }"
+        );
+    }
+
+    #[test]
+    fn inserts_a_missing_theme() {
+        // Into a multi-line object, indented like its members.
+        assert_eq!(
+            set("{\n    \"ui_font_size\": 16\n}\n", Appearance::Light),
+            "{\n    \"theme\": \"Gruvbox Dark\",\n    \"ui_font_size\": 16\n}\n"
+        );
+        // Into an inline object, staying inline.
+        assert_eq!(
+            set("{ \"ui_font_size\": 16 }", Appearance::Light),
+            "{ \"theme\": \"Gruvbox Dark\", \"ui_font_size\": 16 }"
+        );
+        // Into an empty object, or an empty file.
+        assert_eq!(
+            set("{}\n", Appearance::Light),
+            "{\n  \"theme\": \"Gruvbox Dark\"\n}\n"
+        );
+        assert_eq!(
+            set("", Appearance::Light),
+            "{\n  \"theme\": \"Gruvbox Dark\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn keeps_comments() {
+        let text = "// Zed settings\n{\n  // \"theme\": \"Not This\",\n  /* \"theme\": 1, */ \"ui_font_size\": 16, /* size */\n  \"theme\": \"One Light\" // the theme\n}\n";
+        assert_eq!(
+            set(text, Appearance::Light),
+            "// Zed settings\n{\n  // \"theme\": \"Not This\",\n  /* \"theme\": 1, */ \"ui_font_size\": 16, /* size */\n  \"theme\": \"Gruvbox Dark\" // the theme\n}\n"
+        );
+    }
+
+    #[test]
+    fn keeps_trailing_commas() {
+        let text = "{\n  \"languages\": { \"Rust\": { \"tab_size\": 4, }, },\n  \"theme\": \"One Light\",\n}\n";
+        assert_eq!(
+            set(text, Appearance::Light),
+            "{\n  \"languages\": { \"Rust\": { \"tab_size\": 4, }, },\n  \"theme\": \"Gruvbox Dark\",\n}\n"
+        );
+    }
+
+    #[test]
+    fn handles_escaped_quotes() {
+        let text = "{ \"label\": \"say \\\"theme\\\": x\", \"theme\": \"Quoted \\\"Old\\\"\" }";
+        assert_eq!(
+            set_theme(text, "Quoted \"New\"", Appearance::Light).unwrap(),
+            "{ \"label\": \"say \\\"theme\\\": x\", \"theme\": \"Quoted \\\"New\\\"\" }"
+        );
+    }
+
+    #[test]
+    fn reads_the_shown_theme_and_its_pair() {
+        let setting = read_theme_setting("{ \"theme\": \"One Dark\", }")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            setting.shown_and_other(Appearance::Light),
+            (Some("One Dark"), None)
+        );
+
+        let text = "{\n  // pair\n  \"theme\": { \"mode\": \"system\", \"light\": \"One Light\", \"dark\": \"One Dark\" },\n}";
+        let setting = read_theme_setting(text).unwrap().unwrap();
+        assert_eq!(
+            setting.shown_and_other(Appearance::Light),
+            (Some("One Light"), Some("One Dark"))
+        );
+        assert_eq!(
+            setting.shown_and_other(Appearance::Dark),
+            (Some("One Dark"), Some("One Light"))
+        );
+
+        let text = "{ \"theme\": { \"mode\": \"dark\", \"light\": \"One Light\", \"dark\": \"One Dark\" } }";
+        let setting = read_theme_setting(text).unwrap().unwrap();
+        assert_eq!(
+            setting.shown_and_other(Appearance::Light),
+            (Some("One Dark"), Some("One Light"))
+        );
+    }
+
+    #[test]
+    fn reads_no_setting() {
+        assert_eq!(read_theme_setting("{}").unwrap(), None);
+        assert_eq!(read_theme_setting("").unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_a_theme_of_another_type() {
+        assert!(set_theme("{ \"theme\": 3 }", "Gruvbox Dark", Appearance::Light).is_err());
+        assert!(set_theme("[]", "Gruvbox Dark", Appearance::Light).is_err());
+    }
+}